@@ -0,0 +1,255 @@
+/*!
+Generates rust bindings for scilla contracts, to check them into source control.
+
+```text
+$ zilliqa-bindgen --out-dir src/contracts contracts/
+$ zilliqa-bindgen --out-dir src/contracts contracts/ --check
+```
+
+A module is written per contract, plus a `mod.rs` declaring them. The generated code is the same as the one generated
+for `CONTRACTS_PATH` or by `scilla_contract!`, except that the contract code is embedded with `include_str!`, so the
+bindings don't depend on where they were generated.
+
+With `--check`, nothing is written and the command fails if any of the files is missing or out of date. It's meant
+to be run in CI.
+*/
+
+use std::{
+    fs,
+    path::{Component, Path, PathBuf},
+    process::ExitCode,
+};
+
+use anyhow::{bail, Context, Result};
+use clap::{Parser, ValueEnum};
+use convert_case::Casing;
+use zilliqa_rs_codegen::{generate_binding_with_options, Options, TransitionCase};
+
+const HEADER: &str = "// This file is generated by zilliqa-bindgen. Do not edit it manually.\n\n";
+
+#[derive(Debug, Parser)]
+#[command(version, about)]
+struct Args {
+    /// Scilla contracts, or directories of them.
+    #[arg(required = true)]
+    inputs: Vec<PathBuf>,
+
+    /// Directory to write the modules to.
+    #[arg(short, long)]
+    out_dir: PathBuf,
+
+    /// Name of the module of each contract. `{contract}` is replaced with the snake-cased name of the contract.
+    #[arg(long, default_value = "{contract}")]
+    module_name: String,
+
+    /// Casing of the functions generated for transitions.
+    #[arg(long, value_enum, default_value_t = Case::Snake)]
+    transition_case: Case,
+
+    /// Don't generate `deploy_compressed`.
+    #[arg(long)]
+    no_deploy_compressed: bool,
+
+    /// Path of zilliqa-rs in the generated code.
+    #[arg(long, default_value = "::zilliqa_rs")]
+    crate_path: String,
+
+    /// Don't write anything, but fail if the bindings are missing or out of date.
+    #[arg(long)]
+    check: bool,
+}
+
+#[derive(Debug, Clone, Copy, ValueEnum)]
+enum Case {
+    Snake,
+    Camel,
+    Pascal,
+    Preserve,
+}
+
+impl From<Case> for TransitionCase {
+    fn from(case: Case) -> Self {
+        match case {
+            Case::Snake => TransitionCase::Snake,
+            Case::Camel => TransitionCase::Camel,
+            Case::Pascal => TransitionCase::Pascal,
+            Case::Preserve => TransitionCase::Preserve,
+        }
+    }
+}
+
+fn main() -> ExitCode {
+    let args = Args::parse();
+    match run(&args) {
+        Ok(stale) if stale.is_empty() => ExitCode::SUCCESS,
+        Ok(stale) => {
+            eprintln!("Bindings are out of date:");
+            for path in stale {
+                eprintln!("    {}", path.display());
+            }
+            eprintln!("Run zilliqa-bindgen without --check to update them.");
+            ExitCode::FAILURE
+        }
+        Err(e) => {
+            eprintln!("Error: {e:#}");
+            ExitCode::FAILURE
+        }
+    }
+}
+
+/// Generates the bindings and writes them, unless checking. Returns the files which are missing or out of date.
+fn run(args: &Args) -> Result<Vec<PathBuf>> {
+    let files = generate(args)?;
+    if args.check {
+        return Ok(files
+            .into_iter()
+            .filter(|(path, content)| fs::read_to_string(path).ok().as_ref() != Some(content))
+            .map(|(path, _)| path)
+            .collect());
+    }
+
+    fs::create_dir_all(&args.out_dir).context(format!("Failed to create {}", args.out_dir.display()))?;
+    for (path, content) in files {
+        fs::write(&path, content).context(format!("Failed to write {}", path.display()))?;
+        println!("Wrote {}", path.display());
+    }
+    Ok(vec![])
+}
+
+/// Generates the module of every contract and the `mod.rs` declaring them.
+fn generate(args: &Args) -> Result<Vec<(PathBuf, String)>> {
+    let out_dir = fs::canonicalize(&args.out_dir)
+        .or_else(|_| std::path::absolute(&args.out_dir))
+        .context(format!("Failed to resolve {}", args.out_dir.display()))?;
+
+    let mut modules = vec![];
+    let mut files = vec![];
+    for contract_path in contract_paths(&args.inputs)? {
+        let options = Options {
+            transition_case: args.transition_case.into(),
+            deploy_compressed: !args.no_deploy_compressed,
+            include_path: Some(relative_path(&out_dir, &contract_path)),
+        };
+        let binding = generate_binding_with_options(&contract_path, &options)?;
+        let module = args
+            .module_name
+            .replace("{contract}", &binding.contract_name.to_case(convert_case::Case::Snake));
+        if modules.contains(&module) {
+            bail!("More than one contract is generated as module {module}");
+        }
+
+        let code = binding.to_file(&args.crate_path);
+        let file = syn::parse_file(&code).context(format!("Generated invalid rust code for {}", contract_path.display()))?;
+        files.push((
+            args.out_dir.join(format!("{module}.rs")),
+            format!("{HEADER}{}", prettyplease::unparse(&file)),
+        ));
+        modules.push(module);
+    }
+
+    modules.sort();
+    let declarations: String = modules.iter().map(|module| format!("pub mod {module};\n")).collect();
+    files.push((args.out_dir.join("mod.rs"), format!("{HEADER}{declarations}")));
+    Ok(files)
+}
+
+/// Expands the directories among the inputs to the scilla contracts in them.
+fn contract_paths(inputs: &[PathBuf]) -> Result<Vec<PathBuf>> {
+    let mut contracts = vec![];
+    for input in inputs {
+        if input.is_dir() {
+            let mut entries = vec![];
+            for entry in fs::read_dir(input).context(format!("Failed to read files in {}", input.display()))? {
+                let path = entry.context("Failed to get contract entry")?.path();
+                if path.is_file() && path.extension().is_some_and(|extension| extension == "scilla") {
+                    entries.push(path);
+                }
+            }
+            entries.sort();
+            contracts.extend(entries);
+        } else {
+            contracts.push(input.clone());
+        }
+    }
+
+    contracts
+        .into_iter()
+        .map(|path| fs::canonicalize(&path).context(format!("Failed to find {}", path.display())))
+        .collect()
+}
+
+/// Returns the path of `to` relative to the `from` directory, with `/` as the separator so it's the same on every
+/// platform. Both paths must be absolute.
+fn relative_path(from: &Path, to: &Path) -> String {
+    let from: Vec<Component> = from.components().collect();
+    let to: Vec<Component> = to.components().collect();
+    let common = from.iter().zip(&to).take_while(|(a, b)| a == b).count();
+
+    std::iter::repeat_n("..".to_string(), from.len() - common)
+        .chain(
+            to[common..]
+                .iter()
+                .map(|component| component.as_os_str().to_string_lossy().into_owned()),
+        )
+        .collect::<Vec<_>>()
+        .join("/")
+}
+
+#[cfg(test)]
+mod tests {
+    use std::{fs, path::Path};
+
+    use clap::Parser;
+
+    use super::{relative_path, run, Args};
+
+    #[test]
+    fn relative_path_should_walk_up_to_the_common_ancestor() {
+        assert_eq!(
+            relative_path(
+                Path::new("/repo/src/contracts"),
+                Path::new("/repo/contracts/HelloWorld.scilla")
+            ),
+            "../../contracts/HelloWorld.scilla"
+        );
+        assert_eq!(
+            relative_path(Path::new("/repo"), Path::new("/repo/HelloWorld.scilla")),
+            "HelloWorld.scilla"
+        );
+    }
+
+    #[test]
+    fn check_should_report_missing_and_stale_bindings() {
+        let out_dir = std::env::temp_dir().join(format!("zilliqa-bindgen-{}", std::process::id()));
+        let contract = Path::new(env!("CARGO_MANIFEST_DIR")).join("../tests/contracts/HelloWorld.scilla");
+        let args = |check: bool| {
+            let mut args = vec![
+                "zilliqa-bindgen",
+                "--out-dir",
+                out_dir.to_str().unwrap(),
+                contract.to_str().unwrap(),
+            ];
+            if check {
+                args.push("--check");
+            }
+            Args::parse_from(args)
+        };
+
+        assert_eq!(run(&args(true)).unwrap().len(), 2);
+
+        run(&args(false)).unwrap();
+        let binding = fs::read_to_string(out_dir.join("hello_world.rs")).unwrap();
+        let absolute_path = format!("{:?}", fs::canonicalize(&contract).unwrap().display().to_string());
+        assert!(binding.contains("include_str!(") && binding.contains("\"../"), "{binding}");
+        assert!(!binding.contains(&absolute_path), "{binding}");
+        assert!(fs::read_to_string(out_dir.join("mod.rs"))
+            .unwrap()
+            .ends_with("pub mod hello_world;\n"));
+        assert!(run(&args(true)).unwrap().is_empty());
+
+        fs::write(out_dir.join("hello_world.rs"), binding.replace("set_hello", "set_hi")).unwrap();
+        assert_eq!(run(&args(true)).unwrap(), vec![out_dir.join("hello_world.rs")]);
+
+        fs::remove_dir_all(out_dir).unwrap();
+    }
+}
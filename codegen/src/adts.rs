@@ -0,0 +1,245 @@
+use scilla_parser::{ast::nodes::*, Type};
+
+use crate::{events::primitive_type, scilla_type_to_rust_or_value};
+
+/// A user-defined ADT declared in the library of a contract.
+pub(crate) struct Adt {
+    pub(crate) name: String,
+    /// Name of the generated enum. It's prefixed with the name of the contract, as ADTs of different contracts may
+    /// have the same name, or clash with names of the prelude, e.g. `Error`.
+    pub(crate) rust_name: String,
+    /// Constructors and the types of their arguments. Arguments of types which can't be parsed are `None`.
+    pub(crate) constructors: Vec<(String, Vec<Option<Type>>)>,
+}
+
+fn named_type(name: &str, mut arguments: Vec<Option<Type>>) -> Option<Type> {
+    let r#type = match (name, arguments.len()) {
+        ("Bool", 0) => Type::Bool,
+        ("Option", 1) => Type::Option(Box::new(arguments.pop()??)),
+        ("List", 1) => Type::List(Box::new(arguments.pop()??)),
+        ("Pair", 2) | ("Map", 2) => {
+            let second = Box::new(arguments.pop()??);
+            let first = Box::new(arguments.pop()??);
+            if name == "Pair" {
+                Type::Pair(first, second)
+            } else {
+                Type::Map(first, second)
+            }
+        }
+        (name, 0) => match name.strip_prefix("ByStr").map(str::parse) {
+            Some(Ok(size)) => Type::ByStr(size),
+            _ => primitive_type(name).unwrap_or_else(|| Type::Other(name.to_string())),
+        },
+        _ => return None,
+    };
+    Some(r#type)
+}
+
+fn map_key_type(key: &NodeTypeMapKey) -> Option<Type> {
+    match key {
+        NodeTypeMapKey::GenericMapKey(name) | NodeTypeMapKey::EnclosedGenericId(name) => named_type(&name.to_string(), vec![]),
+        NodeTypeMapKey::EnclosedAddressMapKeyType(_) | NodeTypeMapKey::AddressMapKeyType(_) => Some(Type::ByStr(20)),
+    }
+}
+
+fn map_value_type(value: &NodeTypeMapValue) -> Option<Type> {
+    match value {
+        NodeTypeMapValue::MapValueTypeOrEnumLikeIdentifier(name) => named_type(&name.to_string(), vec![]),
+        NodeTypeMapValue::MapKeyValue(entry) => Some(Type::Map(
+            Box::new(map_key_type(&entry.node.key.node)?),
+            Box::new(map_value_type(&entry.node.value.node)?),
+        )),
+        NodeTypeMapValue::MapValueParenthesizedType(value) => map_value_with_arguments_type(&value.node),
+        NodeTypeMapValue::MapValueAddressType(_) => Some(Type::ByStr(20)),
+    }
+}
+
+fn map_value_with_arguments_type(value: &NodeTypeMapValueAllowingTypeArguments) -> Option<Type> {
+    match value {
+        NodeTypeMapValueAllowingTypeArguments::TypeMapValueNoArgs(value) => map_value_type(&value.node),
+        NodeTypeMapValueAllowingTypeArguments::TypeMapValueWithArgs(name, arguments) => named_type(
+            &name.to_string(),
+            arguments
+                .iter()
+                .map(|argument| match &argument.node {
+                    NodeTypeMapValueArguments::EnclosedTypeMapValue(value) => map_value_with_arguments_type(&value.node),
+                    NodeTypeMapValueArguments::GenericMapValueArgument(name) => named_type(&name.to_string(), vec![]),
+                    NodeTypeMapValueArguments::MapKeyValueType(key, value) => Some(Type::Map(
+                        Box::new(map_key_type(&key.node)?),
+                        Box::new(map_value_type(&value.node)?),
+                    )),
+                })
+                .collect(),
+        ),
+    }
+}
+
+fn scilla_type(r#type: &NodeScillaType) -> Option<Type> {
+    match r#type {
+        NodeScillaType::GenericTypeWithArgs(name, arguments) => named_type(
+            &name.to_string(),
+            arguments.iter().map(|argument| type_argument(&argument.node)).collect(),
+        ),
+        NodeScillaType::MapType(key, value) => Some(Type::Map(
+            Box::new(map_key_type(&key.node)?),
+            Box::new(map_value_type(&value.node)?),
+        )),
+        NodeScillaType::EnclosedType(r#type) => scilla_type(&r#type.node),
+        NodeScillaType::ScillaAddresseType(_) => Some(Type::ByStr(20)),
+        _ => None,
+    }
+}
+
+fn type_argument(argument: &NodeTypeArgument) -> Option<Type> {
+    match argument {
+        NodeTypeArgument::EnclosedTypeArgument(r#type) => scilla_type(&r#type.node),
+        NodeTypeArgument::GenericTypeArgument(name) => named_type(&name.to_string(), vec![]),
+        NodeTypeArgument::AddressTypeArgument(_) => Some(Type::ByStr(20)),
+        NodeTypeArgument::MapTypeArgument(key, value) => Some(Type::Map(
+            Box::new(map_key_type(&key.node)?),
+            Box::new(map_value_type(&value.node)?),
+        )),
+        NodeTypeArgument::TemplateTypeArgument(_) => None,
+    }
+}
+
+/// Finds the ADTs declared with `type` in the library of a contract.
+pub(crate) fn contract_adts(program: &NodeProgram, contract_name: &str) -> Vec<Adt> {
+    let Some(library) = &program.library_definition else {
+        return vec![];
+    };
+
+    library
+        .node
+        .definitions
+        .iter()
+        .filter_map(|definition| match &definition.node {
+            NodeLibrarySingleDefinition::TypeDefinition(name, clauses) => Some(Adt {
+                name: name.to_string(),
+                rust_name: format!("{contract_name}{name}"),
+                constructors: clauses
+                    .iter()
+                    .flatten()
+                    .map(|clause| match &clause.node {
+                        NodeTypeAlternativeClause::ClauseType(name) => (name.to_string(), vec![]),
+                        NodeTypeAlternativeClause::ClauseTypeWithArgs(name, arguments) => (
+                            name.to_string(),
+                            arguments.iter().map(|argument| type_argument(&argument.node)).collect(),
+                        ),
+                    })
+                    .collect(),
+            }),
+            _ => None,
+        })
+        .collect()
+}
+
+fn argument_rust_type(r#type: &Option<Type>, adts: &[Adt]) -> String {
+    // Arguments of unknown types are kept as raw scilla values.
+    match r#type {
+        Some(r#type) => scilla_type_to_rust_or_value(r#type, adts),
+        None => "ScillaValue".to_string(),
+    }
+}
+
+fn adt_to_rust_enum(adt: &Adt, adts: &[Adt]) -> String {
+    let Adt { name, rust_name, .. } = adt;
+    let variants = adt
+        .constructors
+        .iter()
+        .map(|(constructor, arguments)| {
+            if arguments.is_empty() {
+                return format!("    {constructor},");
+            }
+            let arguments = arguments
+                .iter()
+                .map(|argument| argument_rust_type(argument, adts))
+                .collect::<Vec<_>>()
+                .join(", ");
+            format!("    {constructor}({arguments}),")
+        })
+        .fold("".to_string(), |acc, e| format!("{acc}\n{e}"));
+
+    let to_value_arms = adt
+        .constructors
+        .iter()
+        .map(|(constructor, arguments)| {
+            let names = (0..arguments.len()).map(|i| format!("arg{i}")).collect::<Vec<_>>();
+            let pattern = if names.is_empty() {
+                format!("Self::{constructor}")
+            } else {
+                format!("Self::{constructor}({})", names.join(", "))
+            };
+            let values = names
+                .iter()
+                .zip(arguments)
+                .map(|(name, argument)| match argument_rust_type(argument, adts).as_str() {
+                    "ScillaValue" => format!("{name}.clone()"),
+                    _ => format!("{name}.to_value()"),
+                })
+                .collect::<Vec<_>>()
+                .join(", ");
+            format!(
+                r#"
+            {pattern} => ScillaValue::Adt(AdtValue {{
+                constructor: "{constructor}".to_string(),
+                argtypes: vec![],
+                arguments: vec![{values}],
+            }}),"#
+            )
+        })
+        .collect::<String>();
+
+    let from_value_arms = adt
+        .constructors
+        .iter()
+        .map(|(constructor, arguments)| {
+            let value = if arguments.is_empty() {
+                format!("Self::{constructor}")
+            } else {
+                let arguments = vec!["arguments.next().unwrap().try_into_rust_type()?"; arguments.len()].join(", ");
+                format!("Self::{constructor}({arguments})")
+            };
+            format!(
+                "\n                ({constructor:?}, {}) => return Ok({value}),",
+                arguments.len()
+            )
+        })
+        .collect::<String>();
+
+    format!(
+        r#"
+#[derive(Debug, Clone)]
+pub enum {rust_name} {{{variants}
+}}
+
+impl ToScillaValue for {rust_name} {{
+    fn to_value(&self) -> ScillaValue {{
+        match self {{{to_value_arms}
+        }}
+    }}
+
+    fn scilla_type() -> String {{
+        "{name}".to_string()
+    }}
+}}
+
+impl TryFromScillaValue for {rust_name} {{
+    #[allow(clippy::single_match, unused_mut, unused_variables)]
+    fn try_from_scilla_value(value: ScillaValue) -> Result<Self, Error> {{
+        if let ScillaValue::Adt(adt) = &value {{
+            let mut arguments = adt.arguments.clone().into_iter();
+            match (adt.constructor_name(), adt.arguments.len()) {{{from_value_arms}
+                _ => {{}}
+            }}
+        }}
+        Err(Error::FailedToParseScillaValue(serde_json::to_string(&value)?, "{name}".to_string()))
+    }}
+}}
+"#
+    )
+}
+
+pub(crate) fn adts_to_rust_enums(adts: &[Adt]) -> String {
+    adts.iter().map(|adt| adt_to_rust_enum(adt, adts)).collect()
+}
@@ -0,0 +1,379 @@
+use std::collections::HashMap;
+
+use convert_case::Casing;
+use scilla_parser::{ast::nodes::*, Contract, Type};
+
+use crate::{adts::Adt, scilla_type_to_rust_or_value};
+
+/// An event emitted by a contract. Types of parameters which can't be inferred are `None`.
+pub(crate) struct Event {
+    name: String,
+    params: Vec<(String, Option<Type>)>,
+}
+
+/// Types of the variables visible at some point of a contract.
+type Scope = HashMap<String, Type>;
+
+fn add_event(events: &mut Vec<Event>, event: Event) {
+    // The same event may be emitted in several places, each of them may tell us about a different parameter.
+    match events.iter_mut().find(|e| e.name == event.name) {
+        Some(existing) => {
+            for (name, r#type) in event.params {
+                match existing.params.iter_mut().find(|(n, _)| *n == name) {
+                    Some((_, existing_type)) if existing_type.is_none() => *existing_type = r#type,
+                    Some(_) => {}
+                    None => existing.params.push((name, r#type)),
+                }
+            }
+        }
+        None => events.push(event),
+    }
+}
+
+pub(crate) fn primitive_type(name: &str) -> Option<Type> {
+    match name {
+        "Int32" => Some(Type::Int32),
+        "Int64" => Some(Type::Int64),
+        "Int128" => Some(Type::Int128),
+        "Int256" => Some(Type::Int256),
+        "Uint32" => Some(Type::Uint32),
+        "Uint64" => Some(Type::Uint64),
+        "Uint128" => Some(Type::Uint128),
+        "Uint256" => Some(Type::Uint256),
+        "String" => Some(Type::String),
+        "BNum" => Some(Type::BNum),
+        _ => None,
+    }
+}
+
+fn literal_type(literal: &NodeValueLiteral) -> Option<Type> {
+    match literal {
+        NodeValueLiteral::LiteralInt(type_name, _) => primitive_type(&type_name.node.to_string()),
+        NodeValueLiteral::LiteralHex(hex) => Some(Type::ByStr(hex.node.trim_start_matches("0x").len() / 2)),
+        NodeValueLiteral::LiteralString(_) => Some(Type::String),
+        NodeValueLiteral::LiteralEmptyMap(_, _) => None,
+    }
+}
+
+fn builtin_type(builtin: &str, arguments: Vec<Option<&Type>>) -> Option<Type> {
+    match builtin {
+        "add" | "sub" | "mul" | "div" | "rem" | "pow" | "isqrt" | "concat" => arguments.into_iter().flatten().next().cloned(),
+        "eq" | "lt" | "blt" => Some(Type::Bool),
+        "badd" => Some(Type::BNum),
+        "bsub" => Some(Type::Int256),
+        "sha256hash" | "keccak256hash" => Some(Type::ByStr(32)),
+        "ripemd160hash" => Some(Type::ByStr(20)),
+        _ => builtin
+            .strip_prefix("to_")
+            .and_then(|t| primitive_type(&t.to_case(convert_case::Case::Pascal)))
+            .map(|t| Type::Option(Box::new(t))),
+    }
+}
+
+fn message_to_event(entries: &[WithMetaData<NodeMessageEntry>], scope: &Scope) -> Option<Event> {
+    let mut name = None;
+    let mut params = vec![];
+    for entry in entries {
+        match &entry.node {
+            NodeMessageEntry::MessageLiteral(key, value) => match (key.node.to_string().as_str(), &value.node) {
+                ("_eventname", NodeValueLiteral::LiteralString(event_name)) => {
+                    name = Some(event_name.node.trim_matches('"').to_string())
+                }
+                (key, value) => params.push((key.to_string(), literal_type(value))),
+            },
+            NodeMessageEntry::MessageVariable(key, value) => {
+                params.push((key.node.to_string(), scope.get(&value.node.to_string()).cloned()))
+            }
+        }
+    }
+    name.map(|name| Event { name, params })
+}
+
+fn infer_expression(expression: &NodeFullExpression, scope: &Scope, events: &mut Vec<Event>) -> Option<Type> {
+    match expression {
+        NodeFullExpression::ExpressionAtomic(atomic) => match &atomic.node {
+            NodeAtomicExpression::AtomicSid(identifier) => scope.get(&identifier.node.to_string()).cloned(),
+            NodeAtomicExpression::AtomicLit(literal) => literal_type(&literal.node),
+        },
+        NodeFullExpression::ExpressionBuiltin { b, xs, .. } => builtin_type(
+            &b.node,
+            xs.node
+                .arguments
+                .iter()
+                .map(|argument| scope.get(&argument.node.to_string()))
+                .collect(),
+        ),
+        NodeFullExpression::LocalVariableDeclaration {
+            identifier_name,
+            expression,
+            containing_expression,
+            ..
+        } => {
+            let mut inner_scope = scope.clone();
+            match infer_expression(&expression.node, scope, events) {
+                Some(r#type) => inner_scope.insert(identifier_name.node.clone(), r#type),
+                None => inner_scope.remove(&identifier_name.node),
+            };
+            infer_expression(&containing_expression.node, &inner_scope, events)
+        }
+        NodeFullExpression::FunctionDeclaration {
+            identier_value,
+            expression,
+            ..
+        } => {
+            let mut inner_scope = scope.clone();
+            inner_scope.remove(&identier_value.node);
+            infer_expression(&expression.node, &inner_scope, events);
+            None
+        }
+        NodeFullExpression::TemplateFunction { expression, .. } => {
+            infer_expression(&expression.node, scope, events);
+            None
+        }
+        NodeFullExpression::Match { clauses, .. } => clauses.iter().fold(None, |r#type, clause| {
+            let clause_type = infer_expression(&clause.node.expression.node, scope, events);
+            r#type.or(clause_type)
+        }),
+        NodeFullExpression::ConstructorCall {
+            identifier_name,
+            argument_list,
+            ..
+        } => match identifier_name.node.to_string().as_str() {
+            "True" | "False" => Some(Type::Bool),
+            "Some" => argument_list
+                .first()
+                .and_then(|argument| scope.get(&argument.node.to_string()))
+                .map(|r#type| Type::Option(Box::new(r#type.clone()))),
+            // Constructors of user-defined ADTs are in the scope.
+            constructor => scope.get(constructor).cloned(),
+        },
+        NodeFullExpression::Message(entries) => {
+            if let Some(event) = message_to_event(entries, scope) {
+                add_event(events, event);
+            }
+            None
+        }
+        _ => None,
+    }
+}
+
+fn infer_statements(block: &NodeStatementBlock, scope: &mut Scope, events: &mut Vec<Event>) {
+    for statement in &block.statements {
+        let (variable, r#type) = match statement {
+            NodeStatement::Load {
+                left_hand_side,
+                right_hand_side,
+            } => (left_hand_side, scope.get(&right_hand_side.node.to_string()).cloned()),
+            NodeStatement::MapGet {
+                left_hand_side,
+                keys,
+                right_hand_side,
+            } => {
+                let mut r#type = scope.get(&right_hand_side.node).cloned();
+                for _ in keys {
+                    r#type = match r#type {
+                        Some(Type::Map(_, value)) => Some(*value),
+                        _ => None,
+                    };
+                }
+                (left_hand_side, r#type.map(|t| Type::Option(Box::new(t))))
+            }
+            NodeStatement::MapGetExists { left_hand_side, .. } => (left_hand_side, Some(Type::Bool)),
+            NodeStatement::ReadFromBC {
+                left_hand_side,
+                type_name,
+                ..
+            } => {
+                let r#type = match type_name.node.to_string().as_str() {
+                    "BLOCKNUMBER" => Some(Type::BNum),
+                    "CHAINID" => Some(Type::Uint32),
+                    "TIMESTAMP" => Some(Type::Option(Box::new(Type::Uint64))),
+                    _ => None,
+                };
+                (left_hand_side, r#type)
+            }
+            NodeStatement::Bind {
+                left_hand_side,
+                right_hand_side,
+            } => (left_hand_side, infer_expression(&right_hand_side.node, scope, events)),
+            NodeStatement::MatchStmt { clauses, .. } => {
+                for clause in clauses {
+                    if let Some(block) = &clause.node.statement_block {
+                        infer_statements(&block.node, &mut scope.clone(), events);
+                    }
+                }
+                continue;
+            }
+            _ => continue,
+        };
+
+        match r#type {
+            Some(r#type) => scope.insert(variable.node.clone(), r#type),
+            None => scope.remove(&variable.node),
+        };
+    }
+}
+
+/// Finds the events a contract emits. Parameter types are inferred from the types of transition parameters, fields
+/// and library values, as scilla doesn't declare them.
+pub(crate) fn contract_events(contract: &Contract, program: &NodeProgram, adts: &[Adt]) -> Vec<Event> {
+    let mut events = vec![];
+    let mut scope: Scope = [
+        ("_sender", Type::ByStr(20)),
+        ("_origin", Type::ByStr(20)),
+        ("_this_address", Type::ByStr(20)),
+        ("_amount", Type::Uint128),
+        ("_balance", Type::Uint128),
+    ]
+    .into_iter()
+    .map(|(name, r#type)| (name.to_string(), r#type))
+    .chain(
+        contract
+            .init_params
+            .iter()
+            .chain(contract.fields.iter())
+            .map(|field| (field.name.clone(), field.r#type.clone())),
+    )
+    // Constructors can't clash with variables, as only constructors start with an uppercase letter.
+    .chain(adts.iter().flat_map(|adt| {
+        adt.constructors
+            .iter()
+            .map(|(constructor, _)| (constructor.clone(), Type::Other(adt.name.clone())))
+    }))
+    .collect();
+
+    if let Some(library) = &program.library_definition {
+        for definition in &library.node.definitions {
+            if let NodeLibrarySingleDefinition::LetDefinition {
+                variable_name,
+                expression,
+                ..
+            } = &definition.node
+            {
+                if let Some(r#type) = infer_expression(&expression.node, &scope, &mut events) {
+                    scope.insert(variable_name.node.clone(), r#type);
+                }
+            }
+        }
+    }
+
+    for component in &program.contract_definition.node.components {
+        let (name, body) = match &component.node {
+            NodeComponentDefinition::TransitionComponent(transition) => (&transition.node.name, &transition.node.body),
+            NodeComponentDefinition::ProcedureComponent(procedure) => (&procedure.node.name, &procedure.node.body),
+        };
+        let name = match &name.node {
+            NodeComponentId::WithTypeLikeName(name) => name.node.to_string(),
+            NodeComponentId::WithRegularId(name) => name.node.clone(),
+        };
+
+        let mut scope = scope.clone();
+        if let Some(transition) = contract.transitions.iter().find(|transition| transition.name == name) {
+            scope.extend(
+                transition
+                    .params
+                    .iter()
+                    .map(|param| (param.name.clone(), param.r#type.clone())),
+            );
+        }
+        if let Some(block) = &body.node.statement_block {
+            infer_statements(&block.node, &mut scope, &mut events);
+        }
+    }
+
+    events
+}
+
+fn event_variant_name(event_name: &str) -> String {
+    let name = event_name
+        .chars()
+        .map(|c| if c.is_ascii_alphanumeric() { c } else { ' ' })
+        .collect::<String>()
+        .to_case(convert_case::Case::Pascal);
+    if name.starts_with(|c: char| c.is_ascii_alphabetic()) {
+        name
+    } else {
+        format!("Event{name}")
+    }
+}
+
+fn event_param_field_name(param_name: &str) -> String {
+    let name = param_name.to_case(convert_case::Case::Snake);
+    match name.as_str() {
+        "as" | "async" | "await" | "box" | "break" | "const" | "continue" | "crate" | "dyn" | "else" | "enum" | "extern"
+        | "false" | "fn" | "for" | "if" | "impl" | "in" | "let" | "loop" | "match" | "mod" | "move" | "mut" | "pub" | "ref"
+        | "return" | "self" | "static" | "struct" | "super" | "trait" | "true" | "type" | "unsafe" | "use" | "where"
+        | "while" | "yield" => format!("{name}_"),
+        _ => name,
+    }
+}
+
+fn event_param_rust_type(r#type: &Option<Type>, adts: &[Adt]) -> String {
+    // Parameters of unknown types are kept as raw scilla values.
+    match r#type {
+        Some(r#type) => scilla_type_to_rust_or_value(r#type, adts),
+        None => "ScillaValue".to_string(),
+    }
+}
+
+pub(crate) fn events_to_rust_enum(events: &[Event], adts: &[Adt], contract_name: &str) -> String {
+    let variants = events
+        .iter()
+        .map(|event| {
+            let variant = event_variant_name(&event.name);
+            if event.params.is_empty() {
+                return format!("    {variant},");
+            }
+            let fields = event
+                .params
+                .iter()
+                .map(|(name, r#type)| format!("{}: {}", event_param_field_name(name), event_param_rust_type(r#type, adts)))
+                .collect::<Vec<_>>()
+                .join(", ");
+            format!("    {variant} {{ {fields} }},")
+        })
+        .fold("".to_string(), |acc, e| format!("{acc}\n{e}"));
+
+    let decode_body = if events.is_empty() {
+        "let _ = entry;\n        Ok(None)".to_string()
+    } else {
+        let arms = events
+            .iter()
+            .map(|event| {
+                let variant = event_variant_name(&event.name);
+                let fields = event
+                    .params
+                    .iter()
+                    .map(|(name, _)| format!("{}: entry.param({name:?})?", event_param_field_name(name)))
+                    .collect::<Vec<_>>()
+                    .join(", ");
+                let value = if fields.is_empty() {
+                    format!("Self::{variant}")
+                } else {
+                    format!("Self::{variant} {{ {fields} }}")
+                };
+                format!("            {:?} => {value},", event.name)
+            })
+            .fold("".to_string(), |acc, e| format!("{acc}\n{e}"));
+        format!(
+            r#"let event = match entry._eventname.as_str() {{{arms}
+            _ => return Ok(None),
+        }};
+        Ok(Some(event))"#
+        )
+    };
+
+    format!(
+        r#"
+#[derive(Debug, Clone)]
+pub enum {contract_name}Event {{{variants}
+}}
+
+impl ScillaEvent for {contract_name}Event {{
+    fn decode(entry: &EventLogEntry) -> Result<Option<Self>, Error> {{
+        {decode_body}
+    }}
+}}
+"#
+    )
+}
@@ -0,0 +1,533 @@
+/*!
+Generate rust bindings for scilla contracts.
+
+This crate holds the code generation shared by the `zilliqa-rs` build script, which generates bindings for the
+contracts in `CONTRACTS_PATH`, the `scilla_contract!` macro of `zilliqa-rs-macros` and the `zilliqa-bindgen` CLI.
+
+The generated code expects the names in [prelude] to be in scope. [Binding::to_module] wraps it in a module which
+imports them.
+*/
+
+mod adts;
+mod events;
+
+use adts::Adt;
+use anyhow::{anyhow, Context, Result};
+use convert_case::Casing;
+use scilla_parser::{
+    ast::nodes::NodeProgram,
+    parser::{lexer::Lexer, parser::ProgramParser},
+    Contract, Field, FieldList, Transition, Type,
+};
+use std::path::Path;
+
+/// Casing of the functions generated for transitions.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub enum TransitionCase {
+    /// `setHello` becomes `set_hello`.
+    #[default]
+    Snake,
+    /// `SetHello` becomes `setHello`.
+    Camel,
+    /// `setHello` becomes `SetHello`.
+    Pascal,
+    /// The name of the transition is kept as is.
+    Preserve,
+}
+
+/// Options of the generated binding.
+#[derive(Debug, Clone)]
+pub struct Options {
+    /// Casing of the functions generated for transitions.
+    pub transition_case: TransitionCase,
+    /// Whether to generate `deploy_compressed` next to `deploy`.
+    pub deploy_compressed: bool,
+    /// If set, the contract code is embedded with `include_str!` of this path, relative to the generated file.
+    /// Otherwise, the contract is read from its path on deployment.
+    pub include_path: Option<String>,
+}
+
+impl Default for Options {
+    fn default() -> Self {
+        Self {
+            transition_case: TransitionCase::default(),
+            deploy_compressed: true,
+            include_path: None,
+        }
+    }
+}
+
+/// Rust binding generated for a scilla contract.
+#[derive(Debug, Clone)]
+pub struct Binding {
+    /// Name of the contract, which is also the name of the generated struct.
+    pub contract_name: String,
+    /// The generated code.
+    pub code: String,
+    /// Types which couldn't be mapped to a rust type. Raw scilla values are used for them instead.
+    pub unmapped_types: Vec<String>,
+}
+
+impl Binding {
+    /// Wraps the code in a private module which imports the [prelude] from the given path of `zilliqa-rs`,
+    /// e.g. `crate` or `::zilliqa_rs`, and re-exports the generated items.
+    pub fn to_module(&self, zilliqa_rs: &str) -> String {
+        let module_name = format!("__scilla_{}", self.contract_name.to_case(convert_case::Case::Snake));
+        format!(
+            "mod {module_name} {{\n{}\n{}}}\npub use {module_name}::*;\n",
+            prelude(zilliqa_rs),
+            self.code
+        )
+    }
+
+    /// Returns the code as the content of a standalone file, importing the [prelude] from the given path of
+    /// `zilliqa-rs`.
+    pub fn to_file(&self, zilliqa_rs: &str) -> String {
+        format!("{}\n{}", prelude(zilliqa_rs), self.code)
+    }
+}
+
+/// Imports of the names the generated code uses, from the given path of `zilliqa-rs`.
+pub fn prelude(zilliqa_rs: &str) -> String {
+    format!(
+        r#"#[allow(unused_imports)]
+use {zilliqa_rs}::{{
+    __private::{{serde, serde_json}},
+    contract::{{
+        compress_contract, transition_call, AdtValue, BaseContract, ContractFactory, ContractFields, Init, ScillaEvent,
+        ScillaValue, ScillaVariable, ToScillaValue, TransitionCall, TryFromScillaValue, TryIntoRustType,
+    }},
+    core::{{BNum, EventLogEntry, ZilAddress}},
+    middlewares::Middleware,
+    primitive_types, Error,
+}};
+#[allow(unused_imports)]
+use std::sync::Arc;
+"#
+    )
+}
+
+/// Parses the scilla contract at the given path and generates its rust binding with the default [Options].
+pub fn generate_binding(contract_path: &Path) -> Result<Binding> {
+    generate_binding_with_options(contract_path, &Options::default())
+}
+
+/// Parses the scilla contract at the given path and generates its rust binding.
+pub fn generate_binding_with_options(contract_path: &Path, options: &Options) -> Result<Binding> {
+    let contract = Contract::parse(contract_path).context(format!("Failed to parse {}", contract_path.display()))?;
+    let code = std::fs::read_to_string(contract_path).context(format!("Failed to read {}", contract_path.display()))?;
+    let program = ProgramParser::new()
+        .parse(&mut vec![], Lexer::new(&code))
+        .map_err(|e| anyhow!("{e:?}"))
+        .context(format!("Failed to parse {}", contract_path.display()))?;
+    let adts = adts::contract_adts(&program, &contract.name);
+    let code = generate_rust_binding(&contract, &program, &adts, contract_path, options)?;
+
+    let mut unmapped_types = vec![];
+    for field in contract
+        .init_params
+        .iter()
+        .chain(contract.fields.iter())
+        .chain(contract.transitions.iter().flat_map(|transition| transition.params.iter()))
+    {
+        collect_unmapped_types(&field.r#type, &adts, &mut unmapped_types);
+    }
+    for (_, arguments) in adts.iter().flat_map(|adt| adt.constructors.iter()) {
+        for argument in arguments.iter().flatten() {
+            collect_unmapped_types(argument, &adts, &mut unmapped_types);
+        }
+    }
+
+    Ok(Binding {
+        contract_name: contract.name,
+        code,
+        unmapped_types,
+    })
+}
+
+fn collect_unmapped_types(scilla_type: &Type, adts: &[Adt], unmapped_types: &mut Vec<String>) {
+    match scilla_type {
+        Type::Other(name) if adts.iter().any(|adt| adt.name == *name) => {}
+        Type::Other(_) => {
+            let scilla_type = scilla_type.to_string();
+            if !unmapped_types.contains(&scilla_type) {
+                unmapped_types.push(scilla_type);
+            }
+        }
+        Type::Map(key, value) | Type::Pair(key, value) => {
+            collect_unmapped_types(key, adts, unmapped_types);
+            collect_unmapped_types(value, adts, unmapped_types);
+        }
+        Type::Option(inner) | Type::List(inner) => collect_unmapped_types(inner, adts, unmapped_types),
+        _ => {}
+    }
+}
+
+fn scilla_type_to_rust(scilla_type: &scilla_parser::Type, adts: &[Adt]) -> String {
+    match scilla_type {
+        scilla_parser::Type::Int32 => "i32".to_string(),
+        scilla_parser::Type::Int64 => "i64".to_string(),
+        scilla_parser::Type::Int128 => "i128".to_string(),
+        scilla_parser::Type::Int256 => "primitive_types::I256".to_string(),
+        scilla_parser::Type::Uint32 => "u32".to_string(),
+        scilla_parser::Type::Uint64 => "u64".to_string(),
+        scilla_parser::Type::Uint128 => "u128".to_string(),
+        scilla_parser::Type::Uint256 => "primitive_types::U256".to_string(),
+        scilla_parser::Type::String => "String".to_string(),
+        scilla_parser::Type::BNum => "BNum".to_string(),
+        scilla_parser::Type::Map(key, value) => format!(
+            "std::collections::HashMap<{}, {}>",
+            scilla_type_to_rust(key, adts),
+            scilla_type_to_rust(value, adts)
+        ),
+        scilla_parser::Type::ByStr(x) if *x == 20 => "ZilAddress".to_string(),
+        scilla_parser::Type::ByStr(_) => "String".to_string(),
+        scilla_parser::Type::Other(name) => match adts.iter().find(|adt| adt.name == *name) {
+            Some(adt) => adt.rust_name.clone(),
+            None => "ScillaVariable".to_string(),
+        },
+        scilla_parser::Type::Bool => "bool".to_string(),
+        scilla_parser::Type::Option(t) => format!("Option<{}>", scilla_type_to_rust(t, adts)),
+        scilla_parser::Type::Pair(a, b) => {
+            format!("({}, {})", scilla_type_to_rust(a, adts), scilla_type_to_rust(b, adts))
+        }
+        scilla_parser::Type::List(t) => format!("Vec<{}>", scilla_type_to_rust(t, adts)),
+    }
+}
+
+fn transition_function_name(transition: &Transition, case: TransitionCase) -> String {
+    match case {
+        TransitionCase::Snake => transition.name.to_case(convert_case::Case::Snake),
+        TransitionCase::Camel => transition.name.to_case(convert_case::Case::Camel),
+        TransitionCase::Pascal => transition.name.to_case(convert_case::Case::Pascal),
+        TransitionCase::Preserve => transition.name.clone(),
+    }
+}
+
+fn transition_to_rust_function(transition: &Transition, adts: &[Adt], case: TransitionCase) -> String {
+    let transition_name_snake = transition.name.to_case(convert_case::Case::Snake);
+    let function_name = transition_function_name(transition, case);
+    let allow_non_snake_case = if function_name == transition_name_snake {
+        ""
+    } else {
+        "\n    #[allow(non_snake_case)]"
+    };
+    format!(
+        r#"{allow_non_snake_case}
+    pub fn {function_name}(&self {}) -> core::cell::RefMut<'_, transition_call::TransitionCall<T>> {{
+        self.{transition_name_snake}.borrow_mut().args(vec![{}]);
+        self.{transition_name_snake}.borrow_mut()
+    }}
+"#,
+        fields_to_parameters_of_functions_signature(&transition.params, adts),
+        fields_to_values(&transition.params, adts)
+    )
+}
+
+fn scilla_type_to_rust_or_value(scilla_type: &Type, adts: &[Adt]) -> String {
+    // Values we can't map to a rust type are kept as raw scilla values.
+    match scilla_type_to_rust(scilla_type, adts) {
+        rust_type if rust_type == "ScillaVariable" => "ScillaValue".to_string(),
+        rust_type => rust_type,
+    }
+}
+
+fn fields_to_contract_state_struct(struct_name: &str, fields: &FieldList, adts: &[Adt]) -> String {
+    let struct_fields = fields
+        .iter()
+        .map(|field| {
+            format!(
+                "    pub {}: {},",
+                field.name,
+                scilla_type_to_rust_or_value(&field.r#type, adts)
+            )
+        })
+        .fold("".to_string(), |acc, e| format!("{acc}\n{e}"));
+    let fields_from_values = fields
+        .iter()
+        .map(|field| format!("            {}: fields.take(\"{}\")?,", field.name, field.name))
+        .fold("".to_string(), |acc, e| format!("{acc}\n{e}"));
+    let fields_param = if fields.is_empty() { "_fields" } else { "mut fields" };
+
+    format!(
+        r#"#[derive(Debug, Clone)]
+pub struct {struct_name} {{{struct_fields}
+}}
+
+impl {struct_name} {{
+    pub fn from_fields({fields_param}: ContractFields) -> Result<Self, Error> {{
+        Ok(Self {{{fields_from_values}
+        }})
+    }}
+}}
+
+impl<'de> serde::Deserialize<'de> for {struct_name} {{
+    fn deserialize<D: serde::Deserializer<'de>>(deserializer: D) -> Result<Self, D::Error> {{
+        Self::from_fields(ContractFields::deserialize(deserializer)?).map_err(serde::de::Error::custom)
+    }}
+}}
+"#
+    )
+}
+
+fn get_contract_init_fields_getters(init_params: &FieldList, adts: &[Adt]) -> String {
+    init_params
+        .iter()
+        .map(|field| {
+            let rust_type = scilla_type_to_rust(&field.r#type, adts);
+            // If rust type is `ScillaVariable` it means we couldn't map the scilla type to a rust one. So we consider it as a string
+            let rust_type = if rust_type == "ScillaVariable" {
+                "String".to_string()
+            } else {
+                rust_type
+            };
+            let field_name = &field.name;
+            format!(
+                r#"
+    pub async fn {field_name}(&self) -> Result<{rust_type}, Error> {{
+        self.base.get_init()
+            .await?
+            .iter()
+            .find(|value| value.vname == "{field_name}").ok_or(Error::NoSuchFieldInContractInit("{field_name}".to_string()))?
+            .value
+            .clone()
+            .try_into_rust_type()
+    }}"#,
+            )
+        })
+        .fold("".to_string(), |acc, e| format!("{acc}\n{e}"))
+}
+
+fn field_to_function_param(field: &Field, adts: &[Adt]) -> String {
+    let field_name = field.name.to_case(convert_case::Case::Snake);
+    let rust_type = scilla_type_to_rust(&field.r#type, adts);
+    format!("{field_name}: {rust_type}",)
+}
+
+fn transitions_as_struct_fields(transitions: &[Transition]) -> String {
+    transitions
+        .iter()
+        .map(|tr| {
+            format!(
+                "{}: core::cell::RefCell<TransitionCall<T>>,",
+                tr.name.to_case(convert_case::Case::Snake)
+            )
+        })
+        .reduce(|acc, e| format!("{acc}\n    {e}"))
+        .unwrap_or_default()
+}
+
+fn fields_to_parameters_of_functions_signature(params: &FieldList, adts: &[Adt]) -> String {
+    params
+        .iter()
+        .map(|field| field_to_function_param(field, adts))
+        .fold("".to_string(), |acc, e| format!("{acc}, {e}"))
+}
+
+fn fields_to_values(params: &FieldList, adts: &[Adt]) -> String {
+    params.iter().fold("".to_string(), |acc, e| {
+        let delim = if acc.is_empty() { "" } else { ", " };
+        let rust_type = scilla_type_to_rust(&e.r#type, adts);
+        match rust_type.as_str() {
+            "ScillaVariable" => {
+                format!(r#"{acc}{delim}{} "#, e.name.to_case(convert_case::Case::Snake))
+            }
+            _ => {
+                format!(
+                    r#"{acc}{delim}ScillaVariable::new("{}".to_string(), "{}".to_string(), {}.to_value()) "#,
+                    e.name,
+                    e.r#type,
+                    e.name.to_case(convert_case::Case::Snake)
+                )
+            }
+        }
+    })
+}
+
+fn transitions_to_transition_call_object(transitions: &[Transition]) -> String {
+    transitions
+        .iter()
+        .map(|tr| {
+            format!(
+                "{}: core::cell::RefCell::new(TransitionCall::new(\"{}\", base.address(), base.client().clone())),",
+                tr.name.to_case(convert_case::Case::Snake),
+                tr.name
+            )
+        })
+        .reduce(|acc, e| format!("{acc}\n            {e}"))
+        .unwrap_or_default()
+}
+
+fn to_string_for_contract_field_getters(contract_fields: &FieldList, adts: &[Adt]) -> String {
+    contract_fields.iter()
+            .map(|field| {
+                let rust_type = scilla_type_to_rust_or_value(&field.r#type, adts);
+                format!(
+                    "    pub async fn {}(&self) -> Result<{rust_type}, Error> {{\n        self.base.get_state_field(\"{}\").await\n    }}",
+                    field.name, field.name
+                )
+            })
+            .fold("".to_string(), |acc, e| format!("{acc}\n{e}"))
+}
+
+fn map_state_index(key_name: &str, key_type: &Type) -> String {
+    // Addresses and byte strings are stored in lowercase, but our addresses are displayed checksummed.
+    match key_type {
+        Type::ByStr(_) => format!("&{key_name}.to_string().to_lowercase()"),
+        _ => format!("&{key_name}.to_string()"),
+    }
+}
+
+fn to_string_for_contract_map_getters(contract_fields: &FieldList, adts: &[Adt]) -> String {
+    contract_fields
+        .iter()
+        .filter_map(|field| {
+            let mut key_types = vec![];
+            let mut value_type = &field.r#type;
+            while let Type::Map(key, value) = value_type {
+                key_types.push(key.as_ref());
+                value_type = value.as_ref();
+            }
+            // Only maps with keys of primitive types can be indexed.
+            if key_types.is_empty() || key_types.iter().any(|key| matches!(key, Type::Other(_))) {
+                return None;
+            }
+
+            let key_names: Vec<String> = match key_types.len() {
+                1 => vec!["key".to_string()],
+                n => (1..=n).map(|i| format!("key{i}")).collect(),
+            };
+            let params = key_names
+                .iter()
+                .zip(&key_types)
+                .map(|(name, key)| format!(", {name}: {}", scilla_type_to_rust(key, adts)))
+                .collect::<String>();
+            let indices = key_names
+                .iter()
+                .zip(&key_types)
+                .map(|(name, key)| map_state_index(name, key))
+                .collect::<Vec<_>>()
+                .join(", ");
+            let value_type = scilla_type_to_rust_or_value(value_type, adts);
+            let field_name = &field.name;
+            Some(format!(
+                r#"
+    pub async fn {field_name}_get(&self{params}) -> Result<Option<{value_type}>, Error> {{
+        self.base.get_map_entry("{field_name}", &[{indices}]).await
+    }}"#
+            ))
+        })
+        .collect()
+}
+fn contract_deploy_function(
+    function_name: &str,
+    deployment_params: &str,
+    deployment_params_for_init: &str,
+    contract_path: &Path,
+    options: &Options,
+    compressed: bool,
+) -> String {
+    let deploy = match &options.include_path {
+        Some(include_path) if compressed => {
+            format!("factory.deploy_str(compress_contract(include_str!({include_path:?}))?, init, None)")
+        }
+        Some(include_path) => format!("factory.deploy_str(include_str!({include_path:?}).to_string(), init, None)"),
+        None => format!("factory.deploy_from_file(&std::path::PathBuf::from({contract_path:?}), init, None, {compressed})"),
+    };
+
+    format!(
+        r#"
+    pub async fn {function_name}(client: Arc<T> {deployment_params}) -> Result<Self, Error> {{
+        let factory = ContractFactory::new(client.clone());
+        let init = Init(vec![
+            ScillaVariable::new("_scilla_version".to_string(), "Uint32".to_string(), "0".to_value()),
+            {deployment_params_for_init}
+        ]);
+
+        Ok(Self::new({deploy}.await?))
+    }}
+"#
+    )
+}
+
+fn generate_rust_binding(
+    contract: &Contract,
+    program: &NodeProgram,
+    adts: &[Adt],
+    contract_path: &Path,
+    options: &Options,
+) -> Result<String> {
+    let contract_name = &contract.name;
+    let transitions_as_fields = transitions_as_struct_fields(&contract.transitions);
+    let contract_deployment_params = fields_to_parameters_of_functions_signature(&contract.init_params, adts);
+    let contract_deployment_params_for_init = fields_to_values(&contract.init_params, adts);
+    let transitions_for_new_function = transitions_to_transition_call_object(&contract.transitions);
+    let contract_field_getters = to_string_for_contract_field_getters(&contract.fields, adts);
+    let contract_map_getters = to_string_for_contract_map_getters(&contract.fields, adts);
+    let contract_state_struct = fields_to_contract_state_struct(&format!("{contract_name}State"), &contract.fields, adts);
+    let contract_init_field_getters = get_contract_init_fields_getters(&contract.init_params, adts);
+    let contract_init_struct = fields_to_contract_state_struct(&format!("{contract_name}Init"), &contract.init_params, adts);
+    let contract_event_enum = events::events_to_rust_enum(&events::contract_events(contract, program, adts), adts, contract_name);
+    let contract_adt_enums = adts::adts_to_rust_enums(adts);
+    let deploy = contract_deploy_function(
+        "deploy",
+        &contract_deployment_params,
+        &contract_deployment_params_for_init,
+        contract_path,
+        options,
+        false,
+    );
+    let deploy_compressed = if options.deploy_compressed {
+        contract_deploy_function(
+            "deploy_compressed",
+            &contract_deployment_params,
+            &contract_deployment_params_for_init,
+            contract_path,
+            options,
+            true,
+        )
+    } else {
+        String::new()
+    };
+    let transitions = contract
+        .transitions
+        .iter()
+        .map(|transition| transition_to_rust_function(transition, adts, options.transition_case))
+        .fold("".to_string(), |acc, e| format!("{acc}{e}"));
+
+    Ok(format!(
+        r#"#[derive(Debug)]
+pub struct {contract_name}<T: Middleware> {{
+    pub base: BaseContract<T>,
+    {transitions_as_fields}
+}}
+
+impl<T: Middleware> {contract_name}<T> {{
+    pub fn attach(address: ZilAddress, client: Arc<T>) -> Self {{
+        Self::new(BaseContract::new(address, client))
+    }}
+{deploy}{deploy_compressed}
+    pub fn address(&self) -> &ZilAddress  {{
+        self.base.address()
+    }}
+
+    pub fn new(base: BaseContract<T>) -> Self {{
+        Self{{
+            {transitions_for_new_function}
+            base,
+        }}
+    }}
+    {transitions}{contract_field_getters}{contract_map_getters}{contract_init_field_getters}
+    pub async fn get_state(&self) -> Result<{contract_name}State, Error> {{
+        self.base.get_state().await
+    }}
+
+    pub async fn get_init(&self) -> Result<{contract_name}Init, Error> {{
+        {contract_name}Init::from_fields(self.base.get_init().await?.into())
+    }}
+}}
+
+{contract_state_struct}
+{contract_init_struct}{contract_event_enum}{contract_adt_enums}"#
+    ))
+}
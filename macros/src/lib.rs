@@ -0,0 +1,75 @@
+/*!
+Procedural macros of zilliqa-rs.
+
+Use them through `zilliqa_rs::contract`, e.g. `zilliqa_rs::contract::scilla_contract!`, rather than depending on this
+crate directly.
+*/
+
+use std::path::Path;
+
+use proc_macro::TokenStream;
+use proc_macro2::TokenStream as TokenStream2;
+use quote::quote;
+use syn::{parse_macro_input, LitStr};
+
+/// Generates the rust binding of a scilla contract in the calling crate.
+///
+/// The path of the contract is relative to the root of the calling crate, i.e. where its `Cargo.toml` is.
+/// The generated items are the same as the ones generated for contracts in `CONTRACTS_PATH`: `<contract>`,
+/// `<contract>State`, `<contract>Init` and `<contract>Event`. The crate is rebuilt whenever the contract changes, and
+/// if the contract can't be parsed, the error is reported at compile time.
+///
+/// # Example
+/// ```ignore
+/// zilliqa_rs::contract::scilla_contract!("contracts/FungibleToken.scilla");
+///
+/// let token = FungibleToken::attach(address, provider);
+/// let balance = token.balances_get(holder).await?;
+/// ```
+#[proc_macro]
+pub fn scilla_contract(input: TokenStream) -> TokenStream {
+    let path = parse_macro_input!(input as LitStr);
+    expand(&path).unwrap_or_else(syn::Error::into_compile_error).into()
+}
+
+fn expand(path: &LitStr) -> syn::Result<TokenStream2> {
+    let manifest_dir = std::env::var("CARGO_MANIFEST_DIR").map_err(|e| syn::Error::new(path.span(), e))?;
+    let contract_path = Path::new(&manifest_dir).join(path.value());
+    let binding = zilliqa_rs_codegen::generate_binding(&contract_path).map_err(|e| {
+        syn::Error::new(
+            path.span(),
+            format!("Failed to generate rust binding for {}. {e:#}", contract_path.display()),
+        )
+    })?;
+    let code: TokenStream2 = binding
+        .to_module("::zilliqa_rs")
+        .parse()
+        .map_err(|e| syn::Error::new(path.span(), format!("Generated invalid rust code. {e}")))?;
+
+    let contract_path = contract_path.to_string_lossy();
+    Ok(quote! {
+        // Makes cargo rebuild the crate when the contract changes.
+        const _: &str = include_str!(#contract_path);
+        #code
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use syn::LitStr;
+
+    use super::expand;
+
+    #[test]
+    fn invalid_contract_should_be_a_compile_error() {
+        let path: LitStr = syn::parse_quote!("Cargo.toml");
+        let error = expand(&path).unwrap_err().to_string();
+        assert!(error.starts_with("Failed to generate rust binding for"), "{error}");
+    }
+
+    #[test]
+    fn missing_contract_should_be_a_compile_error() {
+        let path: LitStr = syn::parse_quote!("NoSuchContract.scilla");
+        assert!(expand(&path).is_err());
+    }
+}
@@ -4,7 +4,7 @@ use crate::{
     core::parse_zil,
     core::{DeployContractResponse, ZilAddress},
     middlewares::Middleware,
-    transaction::{Transaction, TransactionBuilder, TransactionParams},
+    transaction::{PendingTransaction, TransactionBuilder, TransactionParams},
     Error,
 };
 
@@ -141,8 +141,7 @@ impl<T: Middleware> Factory<T> {
             .build();
 
         let response: DeployContractResponse = self.client.send_transaction_without_confirm(tx).await?;
-        let transaction = Transaction::new(response.response.tran_id, self.client.provider());
-        transaction.confirm().await?;
+        PendingTransaction::new(response.response.tran_id, self.client.provider()).await?;
         Ok(BaseContract {
             address: response.contract_address,
             client: self.client.clone(),
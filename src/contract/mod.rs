@@ -38,10 +38,13 @@ this package will parse any contracts in the `CONTRACTS_PATH` and
 generate a corresponding rust struct whose implementation will allow
 you to deploy or call those contracts.
 
-We generate three things for each contract:
+We generate these things for each contract:
 
- * `<contract>State` - a struct to represent the state of a contract.
- * `<contract>Init` - a struct to represent the initialisation parameters of a contract.
+ * `<contract>State` - a struct to represent the state of a contract, with a rust typed field per contract field.
+ * `<contract>Init` - a struct to represent the initialisation parameters of a contract, typed the same way.
+ * `<contract>Event` - an enum of the events the contract emits.
+ * `<contract><type>` - an enum per ADT declared with `type` in the contract library, e.g. `FungibleTokenError`. Fields,
+   parameters and events of these types use them.
  * `<contract>` - an implementation which allows you to deploy, query, or call the contract.
 
 The generated code for [HelloWorld.scilla] is something like this:
@@ -71,7 +74,9 @@ impl<T: Middleware> HelloWorld<T> {
 * The `address` function returns the address of the deployed contract.
 * `set_hello` corresponds to `setHello` transition in the contract. Again, because the transition accepts a string parameter, the `set_hello` function does too.
 * `get_hello` corresponds to the `getHello` transition.
-* The contract has a field named, `welcome_msg`, to get the value of this field, the `welcome_msg` function should be called.
+* The contract has a field named, `welcome_msg`, to get the value of this field, the `welcome_msg` function should be called. Only this field is fetched from the network, not the whole state.
+* Map fields get an extra `<field>_get` function to fetch a single entry, e.g. `balances_get(&self, key: ZilAddress) -> Result<Option<u128>, Error>`
+  for a `balances: Map ByStr20 Uint128` field. Nested maps take a key per level, e.g. `allowances_get(&self, key1: ZilAddress, key2: ZilAddress)`.
 * The contract has an immutable state named, `owner` and we passed the value during deployment. To get the value of the owner, we need to call `owner`
 
 All contracts will have the functions:
@@ -80,9 +85,27 @@ All contracts will have the functions:
 * `address` - to retrieve the contract's address once deployed.
 * `new` - to create an instance of the contract object for deployment.
 * `get_state` - to retrieve the contract state (modelled as a `..State` struct).
+* `get_init` - to retrieve the initialisation parameters of the contract (modelled as a `..Init` struct).
 
 For details, you can run `cargo doc` and then look at the generated documentation.
 
+# Generating rust code with the `scilla_contract!` macro
+
+`CONTRACTS_PATH` is read by the build script of zilliqa-rs, so the bindings end up in `zilliqa_rs::contract` and
+every crate in the dependency graph shares the same set of contracts. The [scilla_contract] macro generates the
+same bindings in your own crate instead, with no environment variable needed:
+
+```rust,ignore
+mod contracts {
+    zilliqa_rs::contract::scilla_contract!("contracts/HelloWorld.scilla");
+}
+
+let contract = contracts::HelloWorld::deploy(Arc::new(provider), owner).await?;
+```
+
+The path is relative to the root of your crate. Your crate is rebuilt whenever the contract changes,
+and if the contract can't be parsed, the error is reported as a compile error at the macro call.
+
 # Deploying the contract
 
 Here is the code example to deploy [HelloWorld]:
@@ -244,6 +267,63 @@ async fn main() -> anyhow::Result<()> {
 }
 ```
 
+## Decoding events
+
+Scilla doesn't declare events, so `build.rs` finds them in the code of the contract and infers the types of their
+parameters from the types of transition parameters, fields and library values. For [FungibleToken], it generates
+something like:
+
+```rust,ignore
+pub enum FungibleTokenEvent {
+    TransferSuccess { sender: ZilAddress, recipient: ZilAddress, amount: u128 },
+    TransferFromSuccess { initiator: ZilAddress, sender: ZilAddress, recipient: ZilAddress, amount: u128 },
+    ...
+}
+```
+
+Parameters whose type can't be inferred are kept as [ScillaValue]. The events of a transaction can be decoded by
+calling [TransactionReceipt::decode_events](crate::core::TransactionReceipt::decode_events):
+
+```no_run
+use std::sync::Arc;
+
+use zilliqa_rs::{
+    contract::{self, FungibleTokenEvent},
+    providers::{Http, Provider},
+    signers::LocalWallet,
+};
+
+#[tokio::main]
+async fn main() -> anyhow::Result<()> {
+    const END_POINT: &str = "http://localhost:5555";
+
+    let wallet = "d96e9eb5b782a80ea153c937fa83e5948485fbfc8b7e7c069d7b914dbc350aba".parse::<LocalWallet>()?;
+
+    let provider = Provider::<Http>::try_from(END_POINT)?
+        .with_chain_id(222)
+        .with_signer(wallet.clone());
+
+    let contract = contract::FungibleToken::deploy(
+        Arc::new(provider),
+        wallet.address.clone(),
+        "Token".to_string(),
+        "TKN".to_string(),
+        2,
+        1000,
+    )
+    .await?;
+
+    let tx = contract.transfer(wallet.address.clone(), 10).call().await?;
+    for event in tx.receipt.decode_events::<FungibleTokenEvent>()? {
+        if let FungibleTokenEvent::TransferSuccess { recipient, amount, .. } = event {
+            println!("{amount} tokens sent to {recipient}");
+        }
+    }
+    Ok(())
+}
+```
+
+[FungibleToken]: https://github.com/Zilliqa/zilliqa-rs/blob/master/tests/contracts/FungibleToken.scilla
 [HelloWorld]: https://github.com/Zilliqa/zilliqa-rs/blob/master/tests/contracts/HelloWorld.scilla
 [HelloWorld.scilla]: https://github.com/Zilliqa/zilliqa-rs/blob/master/tests/contracts/HelloWorld.scilla
 [SendZil]: https://github.com/Zilliqa/zilliqa-rs/blob/master/tests/contracts/SendZil.scilla
@@ -252,7 +332,7 @@ async fn main() -> anyhow::Result<()> {
 pub mod factory;
 pub mod scilla_value;
 pub mod transition_call;
-use std::{ops::Deref, str::FromStr, sync::Arc};
+use std::{collections::HashMap, ops::Deref, str::FromStr, sync::Arc};
 
 pub use factory::Factory as ContractFactory;
 use regex::Regex;
@@ -260,8 +340,9 @@ pub use scilla_value::*;
 use serde::{de::DeserializeOwned, Deserialize, Serialize};
 use serde_json::Value as JsonValue;
 pub use transition_call::*;
+pub use zilliqa_rs_macros::scilla_contract;
 
-use crate::core::{GetTransactionResponse, ZilAddress};
+use crate::core::{EventLogEntry, GetTransactionResponse, ZilAddress};
 use crate::signers::Signer;
 use crate::{middlewares::Middleware, transaction::TransactionParams, Error};
 
@@ -282,6 +363,45 @@ impl Deref for Init {
     }
 }
 
+/// Fields of a contract state or init, used to build the typed `<contract>State` and `<contract>Init` structs.
+///
+/// It can be deserialized from either a contract state, which is a map of field names to values, or a contract
+/// init, which is a list of [ScillaVariable]s.
+#[derive(Debug, Clone, Default)]
+pub struct ContractFields(HashMap<String, ScillaValue>);
+
+impl ContractFields {
+    /// Removes a field and converts it to the given rust type.
+    pub fn take<T: TryFromScillaValue>(&mut self, field_name: &str) -> Result<T, Error> {
+        self.0
+            .remove(field_name)
+            .ok_or_else(|| Error::NoSuchFieldInContractState(field_name.to_string()))?
+            .try_into_rust_type()
+    }
+}
+
+impl From<Vec<ScillaVariable>> for ContractFields {
+    fn from(variables: Vec<ScillaVariable>) -> Self {
+        Self(variables.into_iter().map(|var| (var.vname, var.value)).collect())
+    }
+}
+
+impl<'de> Deserialize<'de> for ContractFields {
+    fn deserialize<D: serde::Deserializer<'de>>(deserializer: D) -> Result<Self, D::Error> {
+        #[derive(Deserialize)]
+        #[serde(untagged)]
+        enum Fields {
+            State(HashMap<String, ScillaValue>),
+            Init(Vec<ScillaVariable>),
+        }
+
+        Ok(match Fields::deserialize(deserializer)? {
+            Fields::State(fields) => Self(fields),
+            Fields::Init(variables) => variables.into(),
+        })
+    }
+}
+
 #[derive(Debug, Serialize)]
 struct Transition {
     #[serde(rename = "_tag")]
@@ -301,6 +421,16 @@ impl<T: Middleware> BaseContract<T> {
         }
     }
 
+    /// Address of the contract.
+    pub fn address(&self) -> &ZilAddress {
+        &self.address
+    }
+
+    /// Client the contract is called with.
+    pub fn client(&self) -> &Arc<T> {
+        &self.client
+    }
+
     /// Call a transition of the contract.
     ///
     /// Arguments:
@@ -343,6 +473,41 @@ impl<T: Middleware> BaseContract<T> {
         Err(Error::NoSuchFieldInContractState(field_name.to_string()))
     }
 
+    /// Retrieves a single field of the contract state and converts it to the given rust type.
+    ///
+    /// Unlike [BaseContract::get_state], only the requested field is downloaded.
+    pub async fn get_state_field<F: TryFromScillaValue>(&self, field_name: &str) -> Result<F, Error> {
+        let mut state: HashMap<String, ScillaValue> = serde_json::from_value(
+            self.client
+                .get_smart_contract_sub_state(&self.address, field_name, &[])
+                .await?,
+        )?;
+        state
+            .remove(field_name)
+            .ok_or_else(|| Error::NoSuchFieldInContractState(field_name.to_string()))?
+            .try_into_rust_type()
+    }
+
+    /// Retrieves a single entry of a map field of the contract state, e.g. `balances[address]`, without fetching the
+    /// whole map.
+    ///
+    /// `indices` are the keys of the entry as they're stored in the contract state, one per level of nested maps.
+    /// Returns `None` if the map has no such entry.
+    pub async fn get_map_entry<F: TryFromScillaValue>(&self, field_name: &str, indices: &[&str]) -> Result<Option<F>, Error> {
+        let state = self
+            .client
+            .get_smart_contract_sub_state(&self.address, field_name, indices)
+            .await?;
+
+        let value = indices.iter().fold(&state[field_name], |value, index| &value[*index]);
+        if value.is_null() {
+            return Ok(None);
+        }
+        Ok(Some(
+            serde_json::from_value::<ScillaValue>(value.clone())?.try_into_rust_type()?,
+        ))
+    }
+
     /// The function `get_init` retrieves the initialization parameters of a smart contract.
     pub async fn get_init(&self) -> Result<Vec<ScillaVariable>, Error> {
         self.client.get_smart_contract_init(&self.address).await
@@ -354,6 +519,12 @@ impl<T: Middleware> BaseContract<T> {
     }
 }
 
+/// A typed event of a contract, implemented by the `<contract>Event` enums generated for scilla contracts.
+pub trait ScillaEvent: Sized {
+    /// Decodes an event log entry. Returns `None` if the entry isn't an event of the contract.
+    fn decode(entry: &EventLogEntry) -> Result<Option<Self>, Error>;
+}
+
 pub fn compress_contract(code: &str) -> Result<String, Error> {
     let remove_comments_regex = Regex::new(r"\(\*.*?\*\)")?;
     let replace_whitespace_regex = Regex::new(r"(?m)(^[ \t]*\r?\n)|([ \t]+$)")?;
@@ -364,7 +535,219 @@ pub fn compress_contract(code: &str) -> Result<String, Error> {
 
 #[cfg(test)]
 mod tests {
-    use crate::contract::compress_contract;
+    use claim::assert_matches;
+    use serde_json::json;
+
+    use std::sync::Arc;
+
+    use crate::{
+        contract::{
+            compress_contract, FungibleToken, FungibleTokenEvent, GetFields, GetFieldsInit, GetFieldsState, HelloWorldEvent,
+            ToScillaValue, UserAdtsDenom, UserAdtsEvent, UserAdtsOrder, UserAdtsState,
+        },
+        core::ZilAddress,
+        providers::{
+            mock::{receipt, typed_fixture, MockClient},
+            Provider,
+        },
+        Error,
+    };
+
+    const ADDRESS: &str = "0x1234567890123456789012345678901234567890";
+
+    #[test]
+    fn decode_events_should_decode_known_events_only() {
+        let receipt = receipt(json!({
+            "event_logs": [
+                {
+                    "_eventname": "TransferSuccess",
+                    "address": "0x2a5e5b0b5e3a4b7c2b3a4e6c43e7d29b5a6e6d0d",
+                    "params": [
+                        {"vname": "sender", "type": "ByStr20", "value": "0x381f4008505e940ad7681ec3468a719060caf796"},
+                        {"vname": "recipient", "type": "ByStr20", "value": "0x9c6bde9a1e1a1d3f6b0a46e8fe0cb1d0db8b1f0a"},
+                        {"vname": "amount", "type": "Uint128", "value": "1000"}
+                    ]
+                },
+                {
+                    "_eventname": "getHello()",
+                    "address": "0x2a5e5b0b5e3a4b7c2b3a4e6c43e7d29b5a6e6d0d",
+                    "params": [{"vname": "msg", "type": "String", "value": "Hello world!"}]
+                }
+            ]
+        }));
+
+        let events = receipt.decode_events::<FungibleTokenEvent>().unwrap();
+        assert_eq!(events.len(), 1);
+        assert_matches!(&events[0], FungibleTokenEvent::TransferSuccess { sender, amount: 1000, .. }
+            if *sender == "0x381f4008505e940ad7681ec3468a719060caf796".parse::<ZilAddress>().unwrap());
+
+        let events = receipt.decode_events::<HelloWorldEvent>().unwrap();
+        assert_matches!(&events[..], [HelloWorldEvent::GetHello { msg }] if msg == "Hello world!");
+    }
+
+    #[test]
+    fn state_should_be_deserialized_into_typed_fields() {
+        let state: GetFieldsState = typed_fixture("get_fields_state");
+        let address = ADDRESS.parse::<ZilAddress>().unwrap();
+
+        assert_eq!(state.field_uint128, 2);
+        assert_eq!(state.field_int64, -4);
+        assert_eq!(state.field_option_bystr20_some, Some(address.clone()));
+        assert_eq!(state.field_option_bystr20_none, None);
+        assert_eq!(state.field_pair, ("Hello".to_string(), 2));
+        assert_eq!(state.balances.get(&address), Some(&1000));
+        assert_eq!(state.field_list, vec![2, 1]);
+    }
+
+    #[test]
+    fn init_should_be_deserialized_into_typed_fields() {
+        let init: GetFieldsInit = serde_json::from_value(json!([
+            {"vname": "_scilla_version", "type": "Uint32", "value": "0"},
+            {"vname": "contract_owner", "type": "ByStr20", "value": ADDRESS},
+            {"vname": "init_supply", "type": "Uint128", "value": "1000"}
+        ]))
+        .unwrap();
+
+        assert_eq!(init.contract_owner, ADDRESS.parse::<ZilAddress>().unwrap());
+        assert_eq!(init.init_supply, 1000);
+    }
+
+    #[tokio::test]
+    async fn field_getters_should_fetch_only_their_field() {
+        let mock = MockClient::default();
+        mock.push(json!({"balances": {ADDRESS: "1000"}}));
+
+        let contract = GetFields::attach(ADDRESS.parse().unwrap(), Arc::new(Provider::new(mock.clone(), 222)));
+        let balances = contract.balances().await.unwrap();
+
+        assert_eq!(balances.get(&ADDRESS.parse().unwrap()), Some(&1000));
+        let (method, params) = &mock.requests()[0];
+        assert_eq!(method, "GetSmartContractSubState");
+        assert_eq!(params, &json!([ADDRESS, "balances", []]));
+    }
+
+    #[tokio::test]
+    async fn map_getters_should_fetch_a_single_entry() {
+        let owner = "0x381f4008505e940ad7681ec3468a719060caf796";
+        let spender = "0x9c6bde9a1e1a1d3f6b0a46e8fe0cb1d0db8b1f0a";
+        let mock = MockClient::default();
+        mock.push(json!({"allowances": {owner: {spender: "250"}}}));
+        mock.push(serde_json::Value::Null);
+
+        let contract = FungibleToken::attach(ADDRESS.parse().unwrap(), Arc::new(Provider::new(mock.clone(), 222)));
+        // Keys are checksummed when parsed, but the contract state keeps them in lowercase.
+        let allowance = contract
+            .allowances_get(owner.parse().unwrap(), spender.parse().unwrap())
+            .await
+            .unwrap();
+        assert_eq!(allowance, Some(250));
+        assert_eq!(contract.balances_get(spender.parse().unwrap()).await.unwrap(), None);
+
+        let requests = mock.requests();
+        assert_eq!(requests[0].0, "GetSmartContractSubState");
+        assert_eq!(requests[0].1, json!([ADDRESS, "allowances", [owner, spender]]));
+        assert_eq!(requests[1].1, json!([ADDRESS, "balances", [spender]]));
+    }
+
+    #[test]
+    fn user_defined_adts_should_be_converted_to_scilla_values() {
+        let order = UserAdtsOrder::Order(UserAdtsDenom::Token(ADDRESS.parse().unwrap()), 100, Some(UserAdtsDenom::Zil));
+
+        assert_eq!(UserAdtsOrder::scilla_type(), "Order");
+        assert_eq!(
+            serde_json::to_value(order.to_value()).unwrap(),
+            json!({
+                "constructor": "Order",
+                "argtypes": [],
+                "arguments": [
+                    {"constructor": "Token", "argtypes": [], "arguments": [ADDRESS.to_lowercase()]},
+                    "100",
+                    {
+                        "constructor": "Some",
+                        "argtypes": ["Denom"],
+                        "arguments": [{"constructor": "Zil", "argtypes": [], "arguments": []}]
+                    }
+                ]
+            })
+        );
+    }
+
+    #[test]
+    fn state_should_be_deserialized_into_user_defined_adts() {
+        // Constructors of user-defined ADTs may be qualified with the address of the contract.
+        let zil = json!({"constructor": format!("{ADDRESS}.Zil"), "argtypes": [], "arguments": []});
+        let state: UserAdtsState = serde_json::from_value(json!({
+            "denom": {"constructor": "Token", "argtypes": [], "arguments": [ADDRESS]},
+            "orders": {"1": {"constructor": "Order", "argtypes": [], "arguments": [zil, "100", {
+                "constructor": "None", "argtypes": ["Denom"], "arguments": []
+            }]}},
+            "denoms": [zil]
+        }))
+        .unwrap();
+
+        assert_matches!(state.denom, UserAdtsDenom::Token(address) if address == ADDRESS.parse::<ZilAddress>().unwrap());
+        assert_matches!(
+            state.orders.get(&1),
+            Some(UserAdtsOrder::Order(UserAdtsDenom::Zil, 100, None))
+        );
+        assert_matches!(&state.denoms[..], [UserAdtsDenom::Zil]);
+
+        let error = serde_json::from_value::<UserAdtsState>(json!({
+            "denom": {"constructor": "Token", "argtypes": [], "arguments": []}, "orders": {}, "denoms": []
+        }))
+        .unwrap_err();
+        assert!(error.to_string().contains("Denom"), "{error}");
+    }
+
+    #[test]
+    fn events_should_decode_user_defined_adts() {
+        let receipt = receipt(json!({
+            "event_logs": [{
+                "_eventname": "DenomSet",
+                "address": ADDRESS,
+                "params": [{"vname": "denom", "type": "Denom", "value": {"constructor": "Zil", "argtypes": [], "arguments": []}}]
+            }]
+        }));
+
+        let events = receipt.decode_events::<UserAdtsEvent>().unwrap();
+        assert_matches!(
+            &events[..],
+            [UserAdtsEvent::DenomSet {
+                denom: UserAdtsDenom::Zil
+            }]
+        );
+    }
+
+    mod macro_bindings {
+        crate::contract::scilla_contract!("tests/contracts/HelloWorld.scilla");
+    }
+
+    #[tokio::test]
+    async fn scilla_contract_macro_should_generate_bindings() {
+        let mock = MockClient::default();
+        mock.push(json!({"welcome_msg": "Hello world!"}));
+
+        let contract = macro_bindings::HelloWorld::attach(ADDRESS.parse().unwrap(), Arc::new(Provider::new(mock.clone(), 222)));
+
+        assert_eq!(contract.welcome_msg().await.unwrap(), "Hello world!");
+        assert_eq!(mock.requests()[0].1, json!([ADDRESS, "welcome_msg", []]));
+    }
+
+    #[test]
+    fn decode_events_should_fail_on_missing_params() {
+        let receipt = receipt(json!({
+            "event_logs": [{
+                "_eventname": "TransferSuccess",
+                "address": "0x2a5e5b0b5e3a4b7c2b3a4e6c43e7d29b5a6e6d0d",
+                "params": [{"vname": "amount", "type": "Uint128", "value": "1000"}]
+            }]
+        }));
+
+        assert_matches!(
+            receipt.decode_events::<FungibleTokenEvent>(),
+            Err(Error::NoSuchParamInEvent(param, _)) if param == "sender"
+        );
+    }
 
     #[test]
     fn compression_1_works() {
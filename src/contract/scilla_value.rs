@@ -45,9 +45,17 @@ impl ScillaVariable {
 
 #[derive(serde::Serialize, Debug, Clone, Deserialize)]
 pub struct AdtValue {
-    constructor: String,
-    argtypes: Vec<String>,
-    arguments: Vec<ScillaValue>,
+    pub constructor: String,
+    pub argtypes: Vec<String>,
+    pub arguments: Vec<ScillaValue>,
+}
+
+impl AdtValue {
+    /// Name of the constructor. Constructors of user-defined ADTs may be qualified with the address of the contract
+    /// declaring them, e.g. `0x1234567890123456789012345678901234567890.Token`, the qualifier is stripped.
+    pub fn constructor_name(&self) -> &str {
+        self.constructor.rsplit_once('.').map_or(&self.constructor, |(_, name)| name)
+    }
 }
 
 // TODO: Set better names for trait functions
@@ -130,6 +138,12 @@ from_scilla_value_for!(String);
 from_scilla_value_for!(ZilAddress);
 from_scilla_value_for!(BNum);
 
+impl TryFromScillaValue for ScillaValue {
+    fn try_from_scilla_value(value: ScillaValue) -> Result<Self, Error> {
+        Ok(value)
+    }
+}
+
 impl<T: ToScillaValue> ToScillaValue for Option<T> {
     fn to_value(&self) -> ScillaValue {
         match self {
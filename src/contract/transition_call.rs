@@ -71,7 +71,6 @@ impl<T: Middleware> TransitionCall<T> {
             })?)
             .build();
 
-        let tx = self.client.send_transaction(tx).await?;
-        tx.confirm().await
+        self.client.send_transaction(tx).await?.await
     }
 }
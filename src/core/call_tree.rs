@@ -0,0 +1,380 @@
+//! Tree of the messages sent during a transaction.
+
+use std::fmt;
+
+use serde::Serialize;
+
+use super::{to_str, EventLogEntry, TransactionReceipt, TransitionEntry, ZilAddress};
+use crate::{
+    contract::{ScillaVariable, TryFromScillaValue, TryIntoRustType},
+    Error,
+};
+
+/// Messages sent during a transaction, rebuilt from the flat `transitions` of a receipt by
+/// [TransactionReceipt::call_tree].
+///
+/// The root is the contract called by the transaction, and every event is attributed to the call which emitted it.
+/// It's displayed as an indented text tree, and it can be serialized to JSON.
+///
+/// A receipt doesn't tell which call emitted an event, nor which call sent a message. Both are inferred:
+/// a message at depth `d` is attributed to the latest call of its sender at depth `d - 1`, and an event to the next
+/// call of its emitter in execution order. So two calls of the same contract at the same depth can't be told apart,
+/// and messages sent by such a contract are attributed to the latest call.
+#[derive(Debug, Clone, Serialize)]
+pub struct CallTree {
+    /// Address of the contract called by the transaction, if the receipt tells it.
+    pub address: Option<ZilAddress>,
+    /// Events emitted by the called contract.
+    pub events: Vec<EventLogEntry>,
+    /// Messages sent by the called contract.
+    pub calls: Vec<CallNode>,
+}
+
+/// A message sent by a contract, and everything that happened when it was processed.
+#[derive(Debug, Clone, Serialize)]
+pub struct CallNode {
+    pub sender: ZilAddress,
+    pub recipient: ZilAddress,
+    /// Transition invoked on the recipient. It's empty for payments to user accounts.
+    pub tag: String,
+    #[serde(serialize_with = "to_str")]
+    pub amount: u128,
+    /// Parameters of the transition, as Scilla values. Use [CallNode::param] to decode one.
+    pub params: Vec<ScillaVariable>,
+    /// Depth of the message. Messages sent by the called contract are at depth 0.
+    pub depth: usize,
+    /// Events emitted by the recipient.
+    pub events: Vec<EventLogEntry>,
+    /// Messages sent by the recipient.
+    pub calls: Vec<CallNode>,
+}
+
+impl CallNode {
+    /// Parses the value of a parameter of the transition.
+    pub fn param<T: TryFromScillaValue>(&self, name: &str) -> Result<T, Error> {
+        self.params
+            .iter()
+            .find(|param| param.vname == name)
+            .ok_or_else(|| Error::NoSuchParamInTransition(name.to_string(), self.tag.clone()))?
+            .value
+            .clone()
+            .try_into_rust_type()
+    }
+}
+
+impl CallTree {
+    /// Rebuilds the call tree of a receipt.
+    pub fn new(receipt: &TransactionReceipt) -> Self {
+        let transitions = receipt.transitions.as_deref().unwrap_or_default();
+        let event_logs = receipt.event_logs.as_deref().unwrap_or_default();
+        let parents = parents(transitions);
+
+        let address = transitions
+            .iter()
+            .find(|entry| entry.depth == 0)
+            .map(|entry| entry.addr.clone())
+            .or_else(|| event_logs.first().map(|event| event.address.clone()));
+
+        // Calls in execution order with the contract executing them. `None` is the called contract itself.
+        let mut calls = vec![(None, address.clone())];
+        execution_order(None, &parents, transitions, &mut calls);
+
+        // Events are logged in execution order, so each event is attributed to the next call executed by its
+        // emitter.
+        let mut events = vec![vec![]; transitions.len() + 1];
+        let mut cursor = 0;
+        for event in event_logs {
+            let emitter =
+                |(_, (_, address)): &(usize, &(Option<usize>, Option<ZilAddress>))| address.as_ref() == Some(&event.address);
+            cursor = calls
+                .iter()
+                .enumerate()
+                .skip(cursor)
+                .find(emitter)
+                .or_else(|| calls.iter().enumerate().find(emitter))
+                .map_or(0, |(position, _)| position);
+            events[calls[cursor].0.map_or(0, |i| i + 1)].push(event.clone());
+        }
+
+        Self {
+            address,
+            events: std::mem::take(&mut events[0]),
+            calls: nodes(None, &parents, transitions, &mut events),
+        }
+    }
+}
+
+/// Finds the message which made the sender of each message execute. A message at depth `d` is sent by the recipient
+/// of a message at depth `d - 1`. Messages sent by the called contract don't have any parent.
+fn parents(transitions: &[TransitionEntry]) -> Vec<Option<usize>> {
+    transitions
+        .iter()
+        .enumerate()
+        .map(|(i, entry)| {
+            let depth = entry.depth.checked_sub(1)?;
+            let candidates = || transitions[..i].iter().enumerate().rev().filter(|(_, e)| e.depth == depth);
+            candidates()
+                .find(|(_, e)| e.msg._recipient == entry.addr)
+                .or_else(|| candidates().next())
+                .map(|(j, _)| j)
+        })
+        .collect()
+}
+
+fn children(parent: Option<usize>, parents: &[Option<usize>]) -> impl Iterator<Item = usize> + '_ {
+    parents.iter().enumerate().filter(move |(_, p)| **p == parent).map(|(i, _)| i)
+}
+
+fn execution_order(
+    parent: Option<usize>,
+    parents: &[Option<usize>],
+    transitions: &[TransitionEntry],
+    calls: &mut Vec<(Option<usize>, Option<ZilAddress>)>,
+) {
+    for i in children(parent, parents) {
+        calls.push((Some(i), Some(transitions[i].msg._recipient.clone())));
+        execution_order(Some(i), parents, transitions, calls);
+    }
+}
+
+/// Builds the nodes of the messages sent with the given parent. The events of message `i` are at `events[i + 1]`.
+fn nodes(
+    parent: Option<usize>,
+    parents: &[Option<usize>],
+    transitions: &[TransitionEntry],
+    events: &mut [Vec<EventLogEntry>],
+) -> Vec<CallNode> {
+    children(parent, parents)
+        .map(|i| {
+            let entry = &transitions[i];
+            CallNode {
+                sender: entry.addr.clone(),
+                recipient: entry.msg._recipient.clone(),
+                tag: entry.msg._tag.clone(),
+                amount: entry.msg._amount,
+                params: entry.msg.params.clone(),
+                depth: entry.depth,
+                events: std::mem::take(&mut events[i + 1]),
+                calls: nodes(Some(i), parents, transitions, events),
+            }
+        })
+        .collect()
+}
+
+fn write_params(f: &mut fmt::Formatter<'_>, params: &[ScillaVariable]) -> fmt::Result {
+    for (i, param) in params.iter().enumerate() {
+        let value = serde_json::to_string(&param.value).map_err(|_| fmt::Error)?;
+        let separator = if i == 0 { "" } else { ", " };
+        write!(f, "{separator}{}: {} = {value}", param.vname, param.r#type)?;
+    }
+    Ok(())
+}
+
+fn write_events(f: &mut fmt::Formatter<'_>, events: &[EventLogEntry], indent: usize) -> fmt::Result {
+    for event in events {
+        write!(f, "\n{:indent$}event {}(", "", event._eventname)?;
+        write_params(f, &event.params)?;
+        write!(f, ")")?;
+    }
+    Ok(())
+}
+
+impl CallNode {
+    fn fmt_indented(&self, f: &mut fmt::Formatter<'_>, indent: usize) -> fmt::Result {
+        let tag = if self.tag.is_empty() { "<payment>" } else { &self.tag };
+        write!(f, "\n{:indent$}{} -> {} {tag}(", "", self.sender, self.recipient)?;
+        write_params(f, &self.params)?;
+        write!(f, ") amount: {}", self.amount)?;
+
+        write_events(f, &self.events, indent + 2)?;
+        for call in &self.calls {
+            call.fmt_indented(f, indent + 2)?;
+        }
+        Ok(())
+    }
+}
+
+impl fmt::Display for CallTree {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match &self.address {
+            Some(address) => write!(f, "{address}")?,
+            None => write!(f, "<unknown contract>")?,
+        }
+
+        write_events(f, &self.events, 2)?;
+        for call in &self.calls {
+            call.fmt_indented(f, 2)?;
+        }
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use claim::assert_matches;
+    use serde_json::json;
+
+    use crate::{
+        core::{TransactionReceipt, ZilAddress},
+        providers::mock,
+        Error,
+    };
+
+    const ROUTER: &str = "0x2a5e5b0b5e3a4b7c2b3a4e6c43e7d29b5a6e6d0d";
+    const PAIR: &str = "0x9bfec715a6bd658fcb62b0f8cc9bfa2ade71434a";
+    const TOKEN: &str = "0x1b38fe2a8bd1a5a2cf6f41bb2d0e45d5b1e1ea37";
+    const USER: &str = "0x381f4008505e940ad7681ec3468a719060caf796";
+
+    fn address(address: &str) -> ZilAddress {
+        address.parse().unwrap()
+    }
+
+    fn event(address: &str, name: &str) -> serde_json::Value {
+        json!({ "address": address, "_eventname": name, "params": [] })
+    }
+
+    fn transition(addr: &str, depth: usize, recipient: &str, tag: &str, amount: &str) -> serde_json::Value {
+        json!({
+            "accepted": false,
+            "addr": addr,
+            "depth": depth,
+            "msg": {
+                "_amount": amount,
+                "_recipient": recipient,
+                "_tag": tag,
+                "params": [{ "vname": "amount", "type": "Uint128", "value": "100" }]
+            }
+        })
+    }
+
+    fn receipt() -> TransactionReceipt {
+        mock::receipt(json!({
+            "event_logs": [
+                event(ROUTER, "SwapStarted"),
+                event(PAIR, "Swapped"),
+                event(TOKEN, "TransferSuccess"),
+                event(ROUTER, "TokensReceived"),
+            ],
+            "transitions": [
+                transition(ROUTER, 0, PAIR, "Swap", "0"),
+                transition(PAIR, 1, TOKEN, "Transfer", "0"),
+                transition(TOKEN, 2, ROUTER, "RecipientAcceptTransfer", "0"),
+                transition(ROUTER, 0, USER, "", "5"),
+            ]
+        }))
+    }
+
+    #[test]
+    fn call_tree_should_nest_messages_by_their_senders() {
+        let tree = receipt().call_tree();
+        assert_eq!(tree.address, Some(address(ROUTER)));
+        assert_eq!(tree.calls.len(), 2);
+
+        let swap = &tree.calls[0];
+        assert_eq!(
+            (swap.sender.clone(), swap.recipient.clone()),
+            (address(ROUTER), address(PAIR))
+        );
+        assert_eq!(swap.tag, "Swap");
+        assert_eq!(swap.param::<u128>("amount").unwrap(), 100);
+        assert_matches!(swap.param::<u128>("to"), Err(Error::NoSuchParamInTransition(param, tag)) if param == "to" && tag == "Swap");
+
+        let transfer = &swap.calls[0];
+        assert_eq!(transfer.recipient, address(TOKEN));
+        assert_eq!(transfer.depth, 1);
+        assert_eq!(transfer.calls[0].tag, "RecipientAcceptTransfer");
+
+        let payment = &tree.calls[1];
+        assert_eq!(
+            (payment.recipient.clone(), payment.tag.as_str(), payment.amount),
+            (address(USER), "", 5)
+        );
+        assert!(payment.calls.is_empty());
+    }
+
+    #[test]
+    fn call_tree_should_attribute_events_to_their_emitting_calls() {
+        let tree = receipt().call_tree();
+        let names = |events: &[crate::core::EventLogEntry]| events.iter().map(|e| e._eventname.clone()).collect::<Vec<_>>();
+
+        assert_eq!(names(&tree.events), ["SwapStarted"]);
+        assert_eq!(names(&tree.calls[0].events), ["Swapped"]);
+        assert_eq!(names(&tree.calls[0].calls[0].events), ["TransferSuccess"]);
+        assert_eq!(names(&tree.calls[0].calls[0].calls[0].events), ["TokensReceived"]);
+        assert!(tree.calls[1].events.is_empty());
+    }
+
+    #[test]
+    fn events_of_a_contract_called_at_different_depths_should_be_attributed_to_each_call() {
+        let receipt = mock::receipt(json!({
+            "event_logs": [
+                event(ROUTER, "SwapStarted"),
+                event(TOKEN, "FirstTransfer"),
+                event(ROUTER, "TokensReceived"),
+                event(TOKEN, "SecondTransfer"),
+            ],
+            "transitions": [
+                transition(ROUTER, 0, TOKEN, "Transfer", "0"),
+                transition(TOKEN, 1, ROUTER, "RecipientAcceptTransfer", "0"),
+                transition(ROUTER, 2, TOKEN, "Transfer", "0"),
+                transition(TOKEN, 3, USER, "", "0"),
+            ]
+        }));
+
+        let tree = receipt.call_tree();
+        let first = &tree.calls[0];
+        let callback = &first.calls[0];
+        let second = &callback.calls[0];
+        assert_eq!(
+            (first.recipient.clone(), second.recipient.clone()),
+            (address(TOKEN), address(TOKEN))
+        );
+        assert_eq!(second.depth, 2);
+        assert_eq!(first.events[0]._eventname, "FirstTransfer");
+        assert_eq!(callback.events[0]._eventname, "TokensReceived");
+        assert_eq!(second.events[0]._eventname, "SecondTransfer");
+        assert_eq!(second.calls[0].recipient, address(USER));
+    }
+
+    #[test]
+    fn call_tree_should_be_rendered_as_an_indented_tree() {
+        let (router, pair, token, user) = (address(ROUTER), address(PAIR), address(TOKEN), address(USER));
+        assert_eq!(
+            receipt().call_tree().to_string(),
+            format!(
+                "{router}
+  event SwapStarted()
+  {router} -> {pair} Swap(amount: Uint128 = \"100\") amount: 0
+    event Swapped()
+    {pair} -> {token} Transfer(amount: Uint128 = \"100\") amount: 0
+      event TransferSuccess()
+      {token} -> {router} RecipientAcceptTransfer(amount: Uint128 = \"100\") amount: 0
+        event TokensReceived()
+  {router} -> {user} <payment>(amount: Uint128 = \"100\") amount: 5"
+            )
+        );
+    }
+
+    #[test]
+    fn call_tree_should_be_serialized_to_json() {
+        let json = serde_json::to_value(receipt().call_tree()).unwrap();
+        assert_eq!(json["events"][0]["_eventname"], "SwapStarted");
+        assert_eq!(json["calls"][0]["tag"], "Swap");
+        assert_eq!(
+            json["calls"][0]["calls"][0]["params"][0],
+            json!({ "vname": "amount", "type": "Uint128", "value": "100" })
+        );
+        assert_eq!(json["calls"][1]["amount"], "5");
+        assert_eq!(json["calls"][1]["depth"], 0);
+    }
+
+    #[test]
+    fn call_tree_of_a_transaction_without_transitions_should_hold_all_events() {
+        let receipt = mock::receipt(json!({ "event_logs": [event(TOKEN, "TransferSuccess")] }));
+
+        let tree = receipt.call_tree();
+        assert_eq!(tree.address, Some(address(TOKEN)));
+        assert_eq!(tree.events.len(), 1);
+        assert!(tree.calls.is_empty());
+    }
+}
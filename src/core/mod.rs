@@ -1,11 +1,13 @@
 //! Shared data types and functionalities.
 
+pub mod call_tree;
 #[doc(hidden)]
 pub mod proto;
 pub mod types;
 pub mod units;
 
 use bech32::{FromBase32, ToBase32, Variant};
+pub use call_tree::*;
 use primitive_types::H160;
 pub use types::*;
 pub use units::*;
@@ -14,10 +16,12 @@ use std::{
     fmt::Display,
     ops::{BitAnd, Deref},
     str::FromStr,
+    time::{Duration, SystemTime},
 };
 
 use serde::de::Error as SerdeError;
 use serde::{Deserialize, Deserializer, Serialize};
+use serde_aux::field_attributes::deserialize_number_from_string;
 use sha2::Digest;
 
 use crate::Error;
@@ -30,7 +34,7 @@ fn is_byte_string(str: &str, len: usize) -> bool {
 }
 
 /// A Type-safe Transaction hash.
-#[derive(Debug, Clone, Deserialize)]
+#[derive(Debug, Clone, PartialEq, Eq, Hash, Deserialize)]
 pub struct TxHash(String);
 
 impl TxHash {
@@ -44,6 +48,11 @@ impl TxHash {
     pub fn is_valid(tx_hash: &str) -> bool {
         is_byte_string(tx_hash, 64)
     }
+
+    /// Computes the hash of a transaction out of its protobuf-encoded core fields.
+    pub(crate) fn from_core_info(core_info: &[u8]) -> Self {
+        Self(hex::encode(sha2::Sha256::digest(core_info)))
+    }
 }
 
 impl FromStr for TxHash {
@@ -96,6 +105,115 @@ impl FromStr for BNum {
     }
 }
 
+/// Time of a block or a transaction, in microseconds since the unix epoch.
+///
+/// Nodes return timestamps as strings, e.g. `"1612477810679440"`. The [Display] implementation gives back
+/// the same string.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Hash, Default, Serialize)]
+pub struct Timestamp(u64);
+
+impl Timestamp {
+    /// Creates a new timestamp out of microseconds since the unix epoch.
+    pub fn from_micros(micros: u64) -> Self {
+        Self(micros)
+    }
+
+    /// Microseconds since the unix epoch.
+    pub fn as_micros(&self) -> u64 {
+        self.0
+    }
+
+    /// Converts the timestamp to a [SystemTime].
+    ///
+    /// # Example
+    /// ```
+    /// use std::time::{Duration, SystemTime};
+    /// use zilliqa_rs::core::Timestamp;
+    ///
+    /// let timestamp = Timestamp::from_micros(1612477810679440);
+    /// assert_eq!(
+    ///     timestamp.to_system_time(),
+    ///     SystemTime::UNIX_EPOCH + Duration::from_micros(1612477810679440)
+    /// );
+    /// ```
+    pub fn to_system_time(&self) -> SystemTime {
+        SystemTime::UNIX_EPOCH + Duration::from_micros(self.0)
+    }
+}
+
+impl Display for Timestamp {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(f, "{}", self.0)
+    }
+}
+
+impl FromStr for Timestamp {
+    type Err = Error;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        Ok(Self(s.parse()?))
+    }
+}
+
+impl<'de> Deserialize<'de> for Timestamp {
+    fn deserialize<D>(deserializer: D) -> Result<Self, D::Error>
+    where
+        D: Deserializer<'de>,
+    {
+        Ok(Self(deserialize_number_from_string(deserializer)?))
+    }
+}
+
+/// Identifies a tx block or a DS block.
+///
+/// It can be parsed from a block number or `"latest"`. Zilliqa RPC methods look blocks up by number only.
+///
+/// # Example
+/// ```
+/// use zilliqa_rs::core::BlockId;
+///
+/// assert_eq!("1002353".parse::<BlockId>().unwrap(), BlockId::Number(1002353));
+/// assert_eq!("latest".parse::<BlockId>().unwrap(), BlockId::Latest);
+/// assert!("-1".parse::<BlockId>().is_err());
+/// ```
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum BlockId {
+    /// The block with the given number.
+    Number(u64),
+    /// The most recent block.
+    Latest,
+}
+
+impl Display for BlockId {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            Self::Number(number) => write!(f, "{number}"),
+            Self::Latest => write!(f, "latest"),
+        }
+    }
+}
+
+impl From<u64> for BlockId {
+    fn from(number: u64) -> Self {
+        Self::Number(number)
+    }
+}
+
+impl FromStr for BlockId {
+    type Err = Error;
+
+    fn from_str(block: &str) -> Result<Self, Self::Err> {
+        if block.eq_ignore_ascii_case("latest") {
+            return Ok(Self::Latest);
+        }
+
+        block
+            .parse()
+            .map(Self::Number)
+            .map_err(|_| Error::InvalidBlockId(block.to_string()))
+    }
+}
+
 #[derive(Debug, Clone)]
 /// secp256k1 (K-256) secret key.
 pub struct PrivateKey(k256::SecretKey);
@@ -355,7 +473,7 @@ impl<'de> Deserialize<'de> for ZilAddress {
     where
         D: Deserializer<'de>,
     {
-        let s: &str = Deserialize::deserialize(deserializer)?;
+        let s: String = Deserialize::deserialize(deserializer)?;
 
         s.parse::<Self>().map_err(D::Error::custom)
     }
@@ -365,7 +483,7 @@ impl<'de> Deserialize<'de> for ZilAddress {
 mod tests {
     use claim::assert_ok;
 
-    use super::{is_byte_string, PrivateKey, PublicKey, TxHash, ZilAddress};
+    use super::{is_byte_string, BlockId, PrivateKey, PublicKey, TxHash, ZilAddress};
 
     #[test]
     fn is_byte_string_should_return_true_for_a_valid_byte_string_with_correct_size() {
@@ -505,4 +623,21 @@ mod tests {
 
         assert_eq!(checksum, ZilAddress::to_checksum_address(address).unwrap())
     }
+
+    #[test]
+    fn block_id_should_be_parsed_from_numbers_and_latest() {
+        assert_eq!("42".parse::<BlockId>().unwrap(), BlockId::Number(42));
+        assert_eq!("Latest".parse::<BlockId>().unwrap(), BlockId::Latest);
+    }
+
+    #[test]
+    fn block_id_should_reject_invalid_input() {
+        assert!("".parse::<BlockId>().is_err());
+        assert!("-1".parse::<BlockId>().is_err());
+        assert!("12ab".parse::<BlockId>().is_err());
+        assert!("99999999999999999999999".parse::<BlockId>().is_err());
+        assert!("bdadfd994f452df803cc223d1f417b02830ac96dbe5edad1b9f8d58613f95206"
+            .parse::<BlockId>()
+            .is_err());
+    }
 }
@@ -38,6 +38,16 @@ pub struct ProtoTransactionCoreInfo {
     pub oneof9: Option<Data>,
 }
 
+#[derive(Clone, PartialEq, prost::Message)]
+pub struct ProtoTransaction {
+    #[prost(bytes = "vec", tag = "1")]
+    pub tranid: Vec<u8>,
+    #[prost(message, optional, tag = "2")]
+    pub info: Option<ProtoTransactionCoreInfo>,
+    #[prost(message, optional, tag = "3")]
+    pub signature: Option<ByteArray>,
+}
+
 #[derive(Clone, PartialEq, prost::Oneof)]
 pub enum Nonce {
     #[prost(uint64, tag = "2")]
@@ -4,11 +4,15 @@ use std::{collections::BTreeMap, fmt};
 
 use primitive_types::H160;
 use prost::Message;
-use serde::{Deserialize, Serializer};
+use serde::{Deserialize, Serialize, Serializer};
 use serde_aux::field_attributes::deserialize_number_from_string;
 
-use super::{proto, TxHash, ZilAddress};
-use crate::{contract::ScillaVariable, transaction::Version};
+use super::{proto, CallTree, PublicKey, Timestamp, TxHash, ZilAddress};
+use crate::{
+    contract::{ScillaEvent, ScillaVariable, TryFromScillaValue, TryIntoRustType},
+    transaction::{SignedTransaction, Version},
+    Error,
+};
 
 #[derive(Debug)]
 pub enum RPCMethod {
@@ -95,22 +99,25 @@ pub struct BalanceResponse {
     pub balance: u128,
 }
 
-#[derive(serde::Serialize, Default, Debug, Clone)]
+#[derive(serde::Serialize, Deserialize, Default, Debug, Clone)]
 #[serde(rename_all = "camelCase")]
 pub struct CreateTransactionRequest {
     pub version: Version,
     pub nonce: u64,
     pub to_addr: ZilAddress,
-    #[serde(serialize_with = "to_str")]
+    #[serde(serialize_with = "to_str", deserialize_with = "deserialize_number_from_string")]
     pub amount: u128,
     pub pub_key: Option<String>,
-    #[serde(serialize_with = "to_str")]
+    #[serde(serialize_with = "to_str", deserialize_with = "deserialize_number_from_string")]
     pub gas_price: u128,
-    #[serde(serialize_with = "to_str")]
+    #[serde(serialize_with = "to_str", deserialize_with = "deserialize_number_from_string")]
     pub gas_limit: u64,
     pub code: Option<String>,
     pub data: Option<String>,
     pub signature: Option<String>,
+    /// The account which should sign the transaction. If it's not set, the default account of the signer is used.
+    #[serde(skip)]
+    pub from: Option<ZilAddress>,
 }
 
 impl CreateTransactionRequest {
@@ -136,6 +143,46 @@ impl CreateTransactionRequest {
         };
         proto.encode_to_vec()
     }
+
+    /// Builds a request out of the core fields of a protobuf-encoded transaction. It's the inverse
+    /// of [CreateTransactionRequest::proto_encode], the signature is not part of the core fields.
+    pub fn proto_decode(info: proto::ProtoTransactionCoreInfo) -> Result<Self, Error> {
+        if info.toaddr.len() != H160::len_bytes() {
+            return Err(Error::InvalidAddress(hex::encode(info.toaddr)));
+        }
+
+        Ok(Self {
+            version: Version::unpack(info.version),
+            nonce: match info.oneof2 {
+                Some(proto::Nonce::Nonce(nonce)) => nonce,
+                None => u64::default(),
+            },
+            to_addr: ZilAddress::try_from(H160::from_slice(&info.toaddr))?,
+            amount: u128_from_be_bytes(info.amount)?,
+            pub_key: info.senderpubkey.map(|pub_key| hex::encode(pub_key.data)),
+            gas_price: u128_from_be_bytes(info.gasprice)?,
+            gas_limit: info.gaslimit,
+            code: info
+                .oneof8
+                .map(|proto::Code::Code(code)| String::from_utf8_lossy(&code).into_owned()),
+            data: info
+                .oneof9
+                .map(|proto::Data::Data(data)| String::from_utf8_lossy(&data).into_owned()),
+            signature: None,
+            from: None,
+        })
+    }
+}
+
+fn u128_from_be_bytes(bytes: Option<proto::ByteArray>) -> Result<u128, Error> {
+    let bytes = bytes.map(|bytes| bytes.data).unwrap_or_default();
+    if bytes.len() > 16 {
+        return Err(Error::ParseOverflow);
+    }
+
+    let mut buffer = [0u8; 16];
+    buffer[16 - bytes.len()..].copy_from_slice(&bytes);
+    Ok(u128::from_be_bytes(buffer))
 }
 
 pub fn to_str<S: Serializer, T: fmt::Display>(data: T, serializer: S) -> Result<S::Ok, S::Error> {
@@ -166,14 +213,14 @@ pub struct BlockchainInfo {
     #[serde(rename = "NumPeers")]
     pub num_peers: u32,
 
-    #[serde(rename = "NumTxBlocks")]
-    pub num_tx_blocks: String,
+    #[serde(rename = "NumTxBlocks", deserialize_with = "deserialize_number_from_string")]
+    pub num_tx_blocks: u64,
 
-    #[serde(rename = "NumDSBlocks")]
-    pub num_dsblocks: String,
+    #[serde(rename = "NumDSBlocks", deserialize_with = "deserialize_number_from_string")]
+    pub num_dsblocks: u64,
 
-    #[serde(rename = "NumTransactions")]
-    pub num_transactions: String,
+    #[serde(rename = "NumTransactions", deserialize_with = "deserialize_number_from_string")]
+    pub num_transactions: u64,
 
     #[serde(rename = "TransactionRate")]
     pub transaction_rate: f32,
@@ -184,17 +231,17 @@ pub struct BlockchainInfo {
     #[serde(rename = "DSBlockRate")]
     pub dsblock_rate: f32,
 
-    #[serde(rename = "CurrentMiniEpoch")]
-    pub current_mini_epoch: String,
+    #[serde(rename = "CurrentMiniEpoch", deserialize_with = "deserialize_number_from_string")]
+    pub current_mini_epoch: u64,
 
-    #[serde(rename = "CurrentDSEpoch")]
-    pub current_dsepoch: String,
+    #[serde(rename = "CurrentDSEpoch", deserialize_with = "deserialize_number_from_string")]
+    pub current_dsepoch: u64,
 
-    #[serde(rename = "NumTxnsDSEpoch")]
-    pub num_txns_dsepoch: String,
+    #[serde(rename = "NumTxnsDSEpoch", deserialize_with = "deserialize_number_from_string")]
+    pub num_txns_dsepoch: u64,
 
-    #[serde(rename = "NumTxnsTxEpoch")]
-    pub num_txns_tx_epoch: String,
+    #[serde(rename = "NumTxnsTxEpoch", deserialize_with = "deserialize_number_from_string")]
+    pub num_txns_tx_epoch: u64,
 
     #[serde(rename = "ShardingStructure")]
     pub sharding_structure: ShardingStructure,
@@ -208,14 +255,14 @@ pub struct ShardingStructure {
 
 #[derive(Deserialize, Debug, Clone)]
 pub struct DsBlockHeader {
-    #[serde(rename = "BlockNum")]
-    pub block_num: String,
+    #[serde(rename = "BlockNum", deserialize_with = "deserialize_number_from_string")]
+    pub block_num: u64,
     #[serde(rename = "Difficulty")]
     pub difficulty: u32,
     #[serde(rename = "DifficultyDS")]
     pub difficulty_ds: u32,
-    #[serde(rename = "GasPrice")]
-    pub gas_price: String,
+    #[serde(rename = "GasPrice", deserialize_with = "deserialize_number_from_string")]
+    pub gas_price: u128,
     #[serde(rename = "LeaderPubKey")]
     pub leader_pub_key: String,
     #[serde(rename = "PoWWinners")]
@@ -223,7 +270,7 @@ pub struct DsBlockHeader {
     #[serde(rename = "PrevHash")]
     pub prev_hash: String,
     #[serde(rename = "Timestamp")]
-    pub timestamp: String,
+    pub timestamp: Timestamp,
 }
 
 #[derive(Deserialize, Debug, Clone)]
@@ -238,8 +285,8 @@ pub struct DsBlockHeaderVerbose {
     pub header: DsBlockHeader,
     #[serde(rename = "CommitteeHash")]
     pub committee_hash: String,
-    #[serde(rename = "EpochNum")]
-    pub epoch_num: String,
+    #[serde(rename = "EpochNum", deserialize_with = "deserialize_number_from_string")]
+    pub epoch_num: u64,
     #[serde(rename = "MembersEjected")]
     pub members_ejected: Vec<String>,
     #[serde(rename = "PoWWinnersIP")]
@@ -300,14 +347,14 @@ pub struct BlockList {
 
 #[derive(Deserialize, Debug, Clone)]
 pub struct TxBlockHeader {
-    #[serde(rename = "BlockNum")]
-    pub block_num: String,
-    #[serde(rename = "DSBlockNum")]
-    pub dsblock_num: String,
-    #[serde(rename = "GasLimit")]
-    pub gas_limit: String,
-    #[serde(rename = "GasUsed")]
-    pub gas_used: String,
+    #[serde(rename = "BlockNum", deserialize_with = "deserialize_number_from_string")]
+    pub block_num: u64,
+    #[serde(rename = "DSBlockNum", deserialize_with = "deserialize_number_from_string")]
+    pub dsblock_num: u64,
+    #[serde(rename = "GasLimit", deserialize_with = "deserialize_number_from_string")]
+    pub gas_limit: u64,
+    #[serde(rename = "GasUsed", deserialize_with = "deserialize_number_from_string")]
+    pub gas_used: u64,
     #[serde(rename = "MbInfoHash")]
     pub mb_info_hash: String,
     #[serde(rename = "MinerPubKey")]
@@ -320,16 +367,16 @@ pub struct TxBlockHeader {
     pub num_txns: u32,
     #[serde(rename = "PrevBlockHash")]
     pub prev_block_hash: String,
-    #[serde(rename = "Rewards")]
-    pub rewards: String,
+    #[serde(rename = "Rewards", deserialize_with = "deserialize_number_from_string")]
+    pub rewards: u128,
     #[serde(rename = "StateDeltaHash")]
     pub state_delta_hash: String,
     #[serde(rename = "StateRootHash")]
     pub state_root_hash: String,
     #[serde(rename = "Timestamp")]
-    pub timestamp: String,
-    #[serde(rename = "TxnFees")]
-    pub txn_fees: String,
+    pub timestamp: Timestamp,
+    #[serde(rename = "TxnFees", deserialize_with = "deserialize_number_from_string")]
+    pub txn_fees: u128,
     #[serde(rename = "Version")]
     pub version: u32,
 }
@@ -388,7 +435,7 @@ pub struct TxBlockVerbose {
 pub struct TxList {
     pub number: u32,
     #[serde(rename = "TxnHashes")]
-    pub txn_hashes: Vec<String>,
+    pub txn_hashes: Vec<TxHash>,
 }
 
 #[derive(Deserialize, Debug, Clone)]
@@ -406,29 +453,67 @@ pub struct ShardInfo {
 #[derive(Deserialize, Debug, Clone)]
 pub struct GetTransactionResponse {
     #[serde(rename = "ID")]
-    pub id: String,
-    pub version: String,
-    pub nonce: String,
+    pub id: TxHash,
+    pub version: Version,
+    #[serde(deserialize_with = "deserialize_number_from_string")]
+    pub nonce: u64,
     #[serde(rename = "toAddr")]
-    pub to_addr: String,
-    pub amount: String,
+    pub to_addr: ZilAddress,
+    #[serde(deserialize_with = "deserialize_number_from_string")]
+    pub amount: u128,
     pub code: Option<String>,
     pub data: Option<String>,
-    #[serde(rename = "gasPrice")]
-    pub gas_price: String,
-    #[serde(rename = "gasLimit")]
-    pub gas_limit: String,
+    #[serde(rename = "gasPrice", deserialize_with = "deserialize_number_from_string")]
+    pub gas_price: u128,
+    #[serde(rename = "gasLimit", deserialize_with = "deserialize_number_from_string")]
+    pub gas_limit: u64,
     pub signature: String,
     #[serde(rename = "senderPubKey")]
     pub sender_pub_key: String,
     pub receipt: TransactionReceipt,
 }
 
+impl GetTransactionResponse {
+    /// Verifies the signature of the transaction against its sender public key, and returns the
+    /// address of the sender.
+    ///
+    /// It returns [Error::InvalidSignature] if the transaction isn't genuinely signed by the sender.
+    pub fn verify_signature(&self) -> Result<ZilAddress, Error> {
+        let signed = SignedTransaction::new(self.to_request())?;
+        ZilAddress::try_from(signed.public_key())
+    }
+
+    /// Recomputes the hash of the transaction locally. It should match the `id` returned by the node.
+    pub fn compute_tx_hash(&self) -> Result<TxHash, Error> {
+        let public_key: PublicKey = self.sender_pub_key.parse()?;
+        let core_info = self.to_request().proto_encode(public_key.to_sec1_bytes().into());
+        Ok(TxHash::from_core_info(&core_info))
+    }
+
+    fn to_request(&self) -> CreateTransactionRequest {
+        CreateTransactionRequest {
+            version: self.version.clone(),
+            nonce: self.nonce,
+            to_addr: self.to_addr.clone(),
+            amount: self.amount,
+            pub_key: Some(self.sender_pub_key.clone()),
+            gas_price: self.gas_price,
+            gas_limit: self.gas_limit,
+            code: self.code.clone(),
+            data: self.data.clone(),
+            signature: Some(self.signature.clone()),
+            from: None,
+        }
+    }
+}
+
 #[derive(Deserialize, Debug, Default, Clone)]
 pub struct TransactionReceipt {
     pub accepted: Option<bool>,
-    pub cumulative_gas: String,
-    pub epoch_num: String,
+    #[serde(deserialize_with = "deserialize_number_from_string")]
+    pub cumulative_gas: u64,
+    #[serde(deserialize_with = "deserialize_number_from_string")]
+    pub epoch_num: u64,
     pub event_logs: Option<Vec<EventLogEntry>>,
     pub exceptions: Option<Vec<ExceptionEntry>>,
     pub success: bool,
@@ -444,36 +529,298 @@ impl TransactionReceipt {
             None
         }
     }
+
+    /// Decodes the events of a contract into its generated event type, e.g. `FungibleTokenEvent`.
+    ///
+    /// Events the type doesn't know are skipped. Note that events emitted by any contract called in the
+    /// transaction are decoded, use [EventLogEntry::address] to tell them apart.
+    pub fn decode_events<E: ScillaEvent>(&self) -> Result<Vec<E>, Error> {
+        self.event_logs
+            .iter()
+            .flatten()
+            .filter_map(|entry| E::decode(entry).transpose())
+            .collect()
+    }
+
+    /// Decodes the error codes of the receipt, grouped by the depth of the message that caused them.
+    pub fn receipt_errors(&self) -> BTreeMap<u64, Vec<ReceiptError>> {
+        self.errors
+            .iter()
+            .flatten()
+            .map(|(depth, codes)| (*depth, codes.iter().map(|code| ReceiptError::from(*code)).collect()))
+            .collect()
+    }
+
+    /// Rebuilds the tree of the messages sent during the transaction, with the events each call emitted.
+    pub fn call_tree(&self) -> CallTree {
+        CallTree::new(self)
+    }
+
+    /// Explains why the transaction failed, combining its errors and exceptions.
+    ///
+    /// It returns `None` if the transaction succeeded.
+    pub fn failure_reason(&self) -> Option<FailureReason> {
+        if self.success {
+            return None;
+        }
+
+        Some(FailureReason {
+            errors: self.receipt_errors(),
+            exceptions: self.exceptions.clone().unwrap_or_default(),
+        })
+    }
+}
+
+/// Error codes of a transaction receipt, reported by the Scilla interpreter or the node executing the transaction.
+///
+/// Codes which aren't documented by Zilliqa are kept as [ReceiptError::Unknown].
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum ReceiptError {
+    CheckerFailed,
+    RunnerFailed,
+    BalanceTransferFailed,
+    ExecuteCmdFailed,
+    ExecuteCmdTimeout,
+    NoGasRemainingFound,
+    NoAcceptedFound,
+    CallContractFailed,
+    CreateContractFailed,
+    JsonOutputCorrupted,
+    ContractNotExist,
+    StateCorrupted,
+    LogEntryInstallFailed,
+    MessageCorrupted,
+    ReceiptIsNull,
+    MaxEdgesReached,
+    ChainCallDiffShard,
+    PreparationFailed,
+    NoOutput,
+    OutputIllegal,
+    MapDepthMissing,
+    GasNotSufficient,
+    InternalError,
+    LibraryAsRecipient,
+    VersionInconsistent,
+    LibraryExtractionFailed,
+    Unknown(u64),
+}
+
+impl ReceiptError {
+    /// Returns the numeric code of the error.
+    pub fn code(&self) -> u64 {
+        match self {
+            Self::CheckerFailed => 0,
+            Self::RunnerFailed => 1,
+            Self::BalanceTransferFailed => 2,
+            Self::ExecuteCmdFailed => 3,
+            Self::ExecuteCmdTimeout => 4,
+            Self::NoGasRemainingFound => 5,
+            Self::NoAcceptedFound => 6,
+            Self::CallContractFailed => 7,
+            Self::CreateContractFailed => 8,
+            Self::JsonOutputCorrupted => 9,
+            Self::ContractNotExist => 10,
+            Self::StateCorrupted => 11,
+            Self::LogEntryInstallFailed => 12,
+            Self::MessageCorrupted => 13,
+            Self::ReceiptIsNull => 14,
+            Self::MaxEdgesReached => 15,
+            Self::ChainCallDiffShard => 16,
+            Self::PreparationFailed => 17,
+            Self::NoOutput => 18,
+            Self::OutputIllegal => 19,
+            Self::MapDepthMissing => 20,
+            Self::GasNotSufficient => 21,
+            Self::InternalError => 22,
+            Self::LibraryAsRecipient => 23,
+            Self::VersionInconsistent => 24,
+            Self::LibraryExtractionFailed => 25,
+            Self::Unknown(code) => *code,
+        }
+    }
+
+    /// Returns the name Zilliqa uses for the error, e.g. `RUNNER_FAILED`.
+    pub fn name(&self) -> &'static str {
+        match self {
+            Self::CheckerFailed => "CHECKER_FAILED",
+            Self::RunnerFailed => "RUNNER_FAILED",
+            Self::BalanceTransferFailed => "BALANCE_TRANSFER_FAILED",
+            Self::ExecuteCmdFailed => "EXECUTE_CMD_FAILED",
+            Self::ExecuteCmdTimeout => "EXECUTE_CMD_TIMEOUT",
+            Self::NoGasRemainingFound => "NO_GAS_REMAINING_FOUND",
+            Self::NoAcceptedFound => "NO_ACCEPTED_FOUND",
+            Self::CallContractFailed => "CALL_CONTRACT_FAILED",
+            Self::CreateContractFailed => "CREATE_CONTRACT_FAILED",
+            Self::JsonOutputCorrupted => "JSON_OUTPUT_CORRUPTED",
+            Self::ContractNotExist => "CONTRACT_NOT_EXIST",
+            Self::StateCorrupted => "STATE_CORRUPTED",
+            Self::LogEntryInstallFailed => "LOG_ENTRY_INSTALL_FAILED",
+            Self::MessageCorrupted => "MESSAGE_CORRUPTED",
+            Self::ReceiptIsNull => "RECEIPT_IS_NULL",
+            Self::MaxEdgesReached => "MAX_EDGES_REACHED",
+            Self::ChainCallDiffShard => "CHAIN_CALL_DIFF_SHARD",
+            Self::PreparationFailed => "PREPARATION_FAILED",
+            Self::NoOutput => "NO_OUTPUT",
+            Self::OutputIllegal => "OUTPUT_ILLEGAL",
+            Self::MapDepthMissing => "MAP_DEPTH_MISSING",
+            Self::GasNotSufficient => "GAS_NOT_SUFFICIENT",
+            Self::InternalError => "INTERNAL_ERROR",
+            Self::LibraryAsRecipient => "LIBRARY_AS_RECIPIENT",
+            Self::VersionInconsistent => "VERSION_INCONSISTENT",
+            Self::LibraryExtractionFailed => "LIBRARY_EXTRACTION_FAILED",
+            Self::Unknown(_) => "UNKNOWN",
+        }
+    }
+
+    /// Returns a human-readable description of the error.
+    pub fn message(&self) -> &'static str {
+        match self {
+            Self::CheckerFailed => "Scilla checker failed",
+            Self::RunnerFailed => "Scilla runner failed",
+            Self::BalanceTransferFailed => "Balance transfer failed",
+            Self::ExecuteCmdFailed => "Failed to execute the Scilla interpreter",
+            Self::ExecuteCmdTimeout => "Scilla interpreter timed out",
+            Self::NoGasRemainingFound => "Remaining gas is missing in the interpreter output",
+            Self::NoAcceptedFound => "Accepted flag is missing in the interpreter output",
+            Self::CallContractFailed => "Contract call failed",
+            Self::CreateContractFailed => "Contract creation failed",
+            Self::JsonOutputCorrupted => "Interpreter output is corrupted",
+            Self::ContractNotExist => "Contract doesn't exist",
+            Self::StateCorrupted => "Contract state is corrupted",
+            Self::LogEntryInstallFailed => "Failed to install an event log entry",
+            Self::MessageCorrupted => "Output message is corrupted",
+            Self::ReceiptIsNull => "Receipt is null",
+            Self::MaxEdgesReached => "Maximum number of message edges is reached",
+            Self::ChainCallDiffShard => "Chain call to a contract in a different shard",
+            Self::PreparationFailed => "Failed to prepare the contract invocation",
+            Self::NoOutput => "Interpreter produced no output",
+            Self::OutputIllegal => "Interpreter output is illegal",
+            Self::MapDepthMissing => "Map depth of a field is missing",
+            Self::GasNotSufficient => "Insufficient gas",
+            Self::InternalError => "Internal error",
+            Self::LibraryAsRecipient => "A library can't be the recipient of a message",
+            Self::VersionInconsistent => "Scilla version is inconsistent",
+            Self::LibraryExtractionFailed => "Failed to extract the contract libraries",
+            Self::Unknown(_) => "Unknown error",
+        }
+    }
+}
+
+impl From<u64> for ReceiptError {
+    fn from(code: u64) -> Self {
+        match code {
+            0 => Self::CheckerFailed,
+            1 => Self::RunnerFailed,
+            2 => Self::BalanceTransferFailed,
+            3 => Self::ExecuteCmdFailed,
+            4 => Self::ExecuteCmdTimeout,
+            5 => Self::NoGasRemainingFound,
+            6 => Self::NoAcceptedFound,
+            7 => Self::CallContractFailed,
+            8 => Self::CreateContractFailed,
+            9 => Self::JsonOutputCorrupted,
+            10 => Self::ContractNotExist,
+            11 => Self::StateCorrupted,
+            12 => Self::LogEntryInstallFailed,
+            13 => Self::MessageCorrupted,
+            14 => Self::ReceiptIsNull,
+            15 => Self::MaxEdgesReached,
+            16 => Self::ChainCallDiffShard,
+            17 => Self::PreparationFailed,
+            18 => Self::NoOutput,
+            19 => Self::OutputIllegal,
+            20 => Self::MapDepthMissing,
+            21 => Self::GasNotSufficient,
+            22 => Self::InternalError,
+            23 => Self::LibraryAsRecipient,
+            24 => Self::VersionInconsistent,
+            25 => Self::LibraryExtractionFailed,
+            code => Self::Unknown(code),
+        }
+    }
+}
+
+impl fmt::Display for ReceiptError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Self::Unknown(code) => write!(f, "Unknown error ({code})"),
+            _ => write!(f, "{} ({})", self.message(), self.name()),
+        }
+    }
+}
+
+/// Why a transaction failed, as returned by [TransactionReceipt::failure_reason].
+///
+/// It's displayed on a single line, e.g. `depth 0: Scilla runner failed (RUNNER_FAILED); line 42: Insufficient balance`.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct FailureReason {
+    /// Errors grouped by the depth of the message that caused them.
+    pub errors: BTreeMap<u64, Vec<ReceiptError>>,
+    /// Exceptions thrown by the contracts.
+    pub exceptions: Vec<ExceptionEntry>,
+}
+
+impl fmt::Display for FailureReason {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let errors = self.errors.iter().map(|(depth, errors)| {
+            let errors = errors.iter().map(ToString::to_string).collect::<Vec<_>>().join(", ");
+            format!("depth {depth}: {errors}")
+        });
+        let exceptions = self
+            .exceptions
+            .iter()
+            .map(|exception| format!("line {}: {}", exception.line, exception.message));
+        let reasons = errors.chain(exceptions).collect::<Vec<_>>();
+
+        if reasons.is_empty() {
+            write!(f, "Transaction failed without any errors")
+        } else {
+            write!(f, "{}", reasons.join("; "))
+        }
+    }
 }
 
 #[derive(Deserialize, Debug, Clone)]
 pub struct TransactionStatus {
     #[serde(rename = "ID")]
-    pub id: String,
+    pub id: TxHash,
     pub _id: StatusID,
-    pub amount: String,
-    #[serde(rename = "epochInserted")]
-    pub epoch_inserted: String,
-    #[serde(rename = "epochUpdated")]
-    pub epoch_updated: String,
-    #[serde(rename = "gasLimit")]
-    pub gas_limit: String,
-    #[serde(rename = "gasPrice")]
-    pub gas_price: String,
+    #[serde(deserialize_with = "deserialize_number_from_string")]
+    pub amount: u128,
+    #[serde(rename = "epochInserted", deserialize_with = "deserialize_number_from_string")]
+    pub epoch_inserted: u64,
+    #[serde(rename = "epochUpdated", deserialize_with = "deserialize_number_from_string")]
+    pub epoch_updated: u64,
+    #[serde(rename = "gasLimit", deserialize_with = "deserialize_number_from_string")]
+    pub gas_limit: u64,
+    #[serde(rename = "gasPrice", deserialize_with = "deserialize_number_from_string")]
+    pub gas_price: u128,
     #[serde(rename = "lastModified")]
-    pub last_modified: String,
+    pub last_modified: Timestamp,
     #[serde(rename = "modificationState")]
     pub modification_state: u32,
-    pub nonce: String,
+    #[serde(deserialize_with = "deserialize_number_from_string")]
+    pub nonce: u64,
     #[serde(rename = "senderAddr")]
-    pub sender_addr: String,
+    pub sender_addr: ZilAddress,
     pub signature: String,
     pub status: u32,
     pub success: bool,
     #[serde(rename = "toAddr")]
-    pub to_addr: String,
-    pub version: String,
-    //   statusMessage: String, // TODO: Fill it like zilliqa-js
+    pub to_addr: ZilAddress,
+    pub version: Version,
+}
+
+impl TransactionStatus {
+    /// Decodes the `status` code of the transaction.
+    pub fn status_code(&self) -> TxStatusCode {
+        TxStatusCode::from(self.status)
+    }
+
+    /// Returns a human-readable description of the `status` code, like `statusMessage` of zilliqa-js.
+    pub fn status_message(&self) -> &'static str {
+        self.status_code().message()
+    }
 }
 
 #[derive(Deserialize, Debug, Clone)]
@@ -482,14 +829,194 @@ pub struct StatusID {
     pub id: String,
 }
 
-#[derive(Deserialize, Debug, Clone)]
+/// Status codes returned by `GetTransactionStatus`.
+///
+/// Codes which aren't documented by Zilliqa are kept as [TxStatusCode::Unknown].
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum TxStatusCode {
+    NotFound,
+    Dispatched,
+    SoftConfirmed,
+    Confirmed,
+    NonceTooHigh,
+    MicroblockGasLimitExceeded,
+    ConsensusFailure,
+    MathError,
+    ScillaInvocationError,
+    ContractInitError,
+    InvalidSourceAccount,
+    GasLimitHigherThanShardGasLimit,
+    UnknownTxType,
+    WrongShard,
+    CrossShardContractCall,
+    CodeSizeExceeded,
+    VerificationFailed,
+    GasLimitTooLow,
+    InsufficientBalance,
+    InsufficientGasForChecker,
+    DuplicateTransaction,
+    HigherGasPriceWithSameNonce,
+    InvalidDestinationAddress,
+    FailedToAddContractAccount,
+    NonceTooLow,
+    InternalError,
+    Unknown(u32),
+}
+
+impl TxStatusCode {
+    /// Returns the numeric code of the status.
+    pub fn code(&self) -> u32 {
+        match self {
+            Self::NotFound => 0,
+            Self::Dispatched => 1,
+            Self::SoftConfirmed => 2,
+            Self::Confirmed => 3,
+            Self::NonceTooHigh => 4,
+            Self::MicroblockGasLimitExceeded => 5,
+            Self::ConsensusFailure => 6,
+            Self::MathError => 10,
+            Self::ScillaInvocationError => 11,
+            Self::ContractInitError => 12,
+            Self::InvalidSourceAccount => 13,
+            Self::GasLimitHigherThanShardGasLimit => 14,
+            Self::UnknownTxType => 15,
+            Self::WrongShard => 16,
+            Self::CrossShardContractCall => 17,
+            Self::CodeSizeExceeded => 18,
+            Self::VerificationFailed => 19,
+            Self::GasLimitTooLow => 20,
+            Self::InsufficientBalance => 21,
+            Self::InsufficientGasForChecker => 22,
+            Self::DuplicateTransaction => 23,
+            Self::HigherGasPriceWithSameNonce => 24,
+            Self::InvalidDestinationAddress => 25,
+            Self::FailedToAddContractAccount => 26,
+            Self::NonceTooLow => 27,
+            Self::InternalError => 255,
+            Self::Unknown(code) => *code,
+        }
+    }
+
+    /// Returns a human-readable description of the status, as worded by zilliqa-js.
+    pub fn message(&self) -> &'static str {
+        match self {
+            Self::NotFound => "Transaction not found",
+            Self::Dispatched => "Pending - Dispatched",
+            Self::SoftConfirmed => "Pending - Soft-confirmed (awaiting Tx block generation)",
+            Self::Confirmed => "Confirmed",
+            Self::NonceTooHigh => "Pending - Nonce is higher than expected",
+            Self::MicroblockGasLimitExceeded => "Pending - Microblock gas limit exceeded",
+            Self::ConsensusFailure => "Pending - Consensus failure in network",
+            Self::MathError => "Rejected - Transaction caused math error",
+            Self::ScillaInvocationError => "Rejected - Scilla invocation error",
+            Self::ContractInitError => "Rejected - Contract account initialization error",
+            Self::InvalidSourceAccount => "Rejected - Invalid source account",
+            Self::GasLimitHigherThanShardGasLimit => "Rejected - Gas limit higher than shard gas limit",
+            Self::UnknownTxType => "Rejected - Unknown transaction type",
+            Self::WrongShard => "Rejected - Transaction sent to wrong shard",
+            Self::CrossShardContractCall => "Rejected - Contract & source account cross-shard issue",
+            Self::CodeSizeExceeded => "Rejected - Code size exceeded limit",
+            Self::VerificationFailed => "Rejected - Transaction verification failed",
+            Self::GasLimitTooLow => "Rejected - Gas limit too low",
+            Self::InsufficientBalance => "Rejected - Insufficient balance",
+            Self::InsufficientGasForChecker => "Rejected - Insufficient gas to invoke Scilla checker",
+            Self::DuplicateTransaction => "Rejected - Duplicate transaction exists",
+            Self::HigherGasPriceWithSameNonce => "Rejected - Transaction with same nonce but same/higher gas price exists",
+            Self::InvalidDestinationAddress => "Rejected - Invalid destination address",
+            Self::FailedToAddContractAccount => "Rejected - Failed to add contract account to state",
+            Self::NonceTooLow => "Rejected - Nonce is lower than expected",
+            Self::InternalError => "Rejected - Internal error",
+            Self::Unknown(_) => "Unknown status",
+        }
+    }
+
+    /// Checks if the transaction is known to the node but not processed yet. It may still be confirmed or rejected.
+    pub fn is_pending(&self) -> bool {
+        matches!(
+            self,
+            Self::Dispatched
+                | Self::SoftConfirmed
+                | Self::NonceTooHigh
+                | Self::MicroblockGasLimitExceeded
+                | Self::ConsensusFailure
+        )
+    }
+
+    /// Checks if the transaction is rejected.
+    pub fn is_rejected(&self) -> bool {
+        matches!(self.code(), 10..=27 | 255)
+    }
+
+    /// Checks if the status can't change anymore, i.e. the transaction is either confirmed or rejected.
+    pub fn is_final(&self) -> bool {
+        *self == Self::Confirmed || self.is_rejected()
+    }
+}
+
+impl From<u32> for TxStatusCode {
+    fn from(code: u32) -> Self {
+        match code {
+            0 => Self::NotFound,
+            1 => Self::Dispatched,
+            2 => Self::SoftConfirmed,
+            3 => Self::Confirmed,
+            4 => Self::NonceTooHigh,
+            5 => Self::MicroblockGasLimitExceeded,
+            6 => Self::ConsensusFailure,
+            10 => Self::MathError,
+            11 => Self::ScillaInvocationError,
+            12 => Self::ContractInitError,
+            13 => Self::InvalidSourceAccount,
+            14 => Self::GasLimitHigherThanShardGasLimit,
+            15 => Self::UnknownTxType,
+            16 => Self::WrongShard,
+            17 => Self::CrossShardContractCall,
+            18 => Self::CodeSizeExceeded,
+            19 => Self::VerificationFailed,
+            20 => Self::GasLimitTooLow,
+            21 => Self::InsufficientBalance,
+            22 => Self::InsufficientGasForChecker,
+            23 => Self::DuplicateTransaction,
+            24 => Self::HigherGasPriceWithSameNonce,
+            25 => Self::InvalidDestinationAddress,
+            26 => Self::FailedToAddContractAccount,
+            27 => Self::NonceTooLow,
+            255 => Self::InternalError,
+            code => Self::Unknown(code),
+        }
+    }
+}
+
+impl fmt::Display for TxStatusCode {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Self::Unknown(code) => write!(f, "Unknown status ({code})"),
+            _ => write!(f, "{}", self.message()),
+        }
+    }
+}
+
+#[derive(Serialize, Deserialize, Debug, Clone)]
 pub struct EventLogEntry {
-    pub address: String,
+    pub address: ZilAddress,
     pub _eventname: String,
     pub params: Vec<ScillaVariable>,
 }
 
-#[derive(Deserialize, Debug, Clone)]
+impl EventLogEntry {
+    /// Parses the value of a parameter of the event.
+    pub fn param<T: TryFromScillaValue>(&self, name: &str) -> Result<T, Error> {
+        self.params
+            .iter()
+            .find(|param| param.vname == name)
+            .ok_or_else(|| Error::NoSuchParamInEvent(name.to_string(), self._eventname.clone()))?
+            .value
+            .clone()
+            .try_into_rust_type()
+    }
+}
+
+#[derive(Deserialize, Debug, Clone, PartialEq, Eq)]
 pub struct ExceptionEntry {
     pub line: u32,
     pub message: String,
@@ -498,15 +1025,16 @@ pub struct ExceptionEntry {
 #[derive(Deserialize, Debug, Clone)]
 pub struct TransitionEntry {
     pub accepted: bool,
-    pub addr: String,
+    pub addr: ZilAddress,
     pub depth: usize,
     pub msg: TransitionMsg,
 }
 
 #[derive(Deserialize, Debug, Clone)]
 pub struct TransitionMsg {
-    pub _amount: String,
-    pub _recipient: String,
+    #[serde(deserialize_with = "deserialize_number_from_string")]
+    pub _amount: u128,
+    pub _recipient: ZilAddress,
     pub _tag: String,
     pub params: Vec<ScillaVariable>,
 }
@@ -528,7 +1056,7 @@ pub struct TransactionsForTxBlockEx {
     #[serde(rename = "NumPages")]
     pub num_pages: u32,
     #[serde(rename = "Transactions")]
-    pub transactions: Vec<Vec<String>>,
+    pub transactions: Vec<Vec<TxHash>>,
 }
 
 #[derive(Deserialize, Debug, Clone)]
@@ -546,11 +1074,478 @@ pub struct SmartContractAddress {
 
 #[derive(Deserialize, Debug, Clone)]
 pub struct GetCurrentDsCommResponse {
-    #[serde(rename = "CurrentDSEpoch")]
-    pub current_ds_epoch: String,
-    #[serde(rename = "CurrentTxEpoch")]
-    pub current_tx_epoch: String,
+    #[serde(rename = "CurrentDSEpoch", deserialize_with = "deserialize_number_from_string")]
+    pub current_ds_epoch: u64,
+    #[serde(rename = "CurrentTxEpoch", deserialize_with = "deserialize_number_from_string")]
+    pub current_tx_epoch: u64,
     #[serde(rename = "NumOfDSGuard")]
     pub number_of_ds_guard: u16,
     pub dscomm: Vec<String>,
 }
+
+/// A topic to subscribe to on the websocket API of a zilliqa node.
+#[derive(Serialize, Debug, Clone, PartialEq)]
+#[serde(tag = "query")]
+pub enum SubscriptionQuery {
+    /// Get notified of every new tx block.
+    NewBlock,
+    /// Get notified of events emitted by any of the given contracts.
+    EventLog { addresses: Vec<ZilAddress> },
+    /// Cancel a previous subscription, `type` is the name of its topic.
+    Unsubscribe {
+        #[serde(rename = "type")]
+        topic: String,
+    },
+}
+
+impl SubscriptionQuery {
+    /// Returns the name of the topic as used by the websocket API.
+    pub fn topic(&self) -> &str {
+        match self {
+            Self::NewBlock => "NewBlock",
+            Self::EventLog { .. } => "EventLog",
+            Self::Unsubscribe { .. } => "Unsubscribe",
+        }
+    }
+}
+
+/// The payload of a notification for a single topic.
+///
+/// The websocket API pushes messages like `{"type": "Notification", "values": [...]}`, every item in
+/// `values` is one of these.
+#[derive(Deserialize, Debug, Clone)]
+#[serde(tag = "query", content = "value")]
+pub enum SubscriptionValue {
+    NewBlock(Box<NewBlockNotification>),
+    EventLog(Vec<ContractEventLogs>),
+    Unsubscribe(Vec<String>),
+}
+
+#[derive(Deserialize, Debug, Clone)]
+pub struct NewBlockNotification {
+    #[serde(rename = "TxBlock")]
+    pub tx_block: TxBlock,
+    #[serde(rename = "TxHashes")]
+    pub tx_hashes: Vec<Vec<TxHash>>,
+}
+
+/// Events emitted by a single contract, as pushed by an `EventLog` subscription.
+#[derive(Deserialize, Debug, Clone)]
+pub struct ContractEventLogs {
+    pub address: ZilAddress,
+    pub event_logs: Vec<ContractEvent>,
+}
+
+#[derive(Deserialize, Debug, Clone)]
+pub struct ContractEvent {
+    pub _eventname: String,
+    pub params: Vec<ScillaVariable>,
+}
+
+#[cfg(test)]
+mod tests {
+    use claim::assert_matches;
+    use serde_json::json;
+
+    use crate::{
+        core::{Timestamp, TxHash, ZilAddress},
+        providers::mock::{fixture, receipt, typed_fixture},
+        signers::LocalWallet,
+        transaction::Version,
+        Error,
+    };
+
+    use super::{
+        BlockchainInfo, CreateTransactionRequest, DsBlock, ExceptionEntry, GetCurrentDsCommResponse, GetTransactionResponse,
+        ReceiptError, TransactionStatus, TransactionsForTxBlockEx, TxBlock, TxStatusCode,
+    };
+
+    fn address(address: &str) -> ZilAddress {
+        address.parse().unwrap()
+    }
+
+    fn fetched_transaction() -> (LocalWallet, GetTransactionResponse) {
+        let wallet: LocalWallet = "d96e9eb5b782a80ea153c937fa83e5948485fbfc8b7e7c069d7b914dbc350aba"
+            .parse()
+            .unwrap();
+        let signed = wallet
+            .sign_request(CreateTransactionRequest {
+                version: Version::new(222),
+                nonce: 7,
+                to_addr: "0x381f4008505e940ad7681ec3468a719060caf796".parse().unwrap(),
+                amount: 1_000_000_000_000,
+                gas_price: 2_000_000_000,
+                gas_limit: 50,
+                ..Default::default()
+            })
+            .unwrap();
+
+        // The way nodes return transactions: numbers as strings, keys and signatures prefixed by 0x.
+        let response = serde_json::from_value(json!({
+            "ID": signed.tx_hash().to_string(),
+            "version": "14548993",
+            "nonce": "7",
+            "toAddr": "381f4008505e940ad7681ec3468a719060caf796",
+            "amount": "1000000000000",
+            "gasPrice": "2000000000",
+            "gasLimit": "50",
+            "senderPubKey": format!("0x{}", signed.public_key()),
+            "signature": format!("0x{}", hex::encode(signed.signature().to_bytes())),
+            "receipt": {"cumulative_gas": "50", "epoch_num": "10", "success": true}
+        }))
+        .unwrap();
+        (wallet, response)
+    }
+
+    #[test]
+    fn verify_signature_should_return_the_sender_address() {
+        let (wallet, tx) = fetched_transaction();
+        assert_eq!(tx.verify_signature().unwrap(), wallet.address);
+    }
+
+    #[test]
+    fn verify_signature_should_fail_for_tampered_transaction() {
+        let (_, mut tx) = fetched_transaction();
+        tx.amount = 2_000_000_000_000;
+        assert_matches!(tx.verify_signature(), Err(Error::InvalidSignature));
+    }
+
+    #[test]
+    fn compute_tx_hash_should_match_the_transaction_id() {
+        let (_, tx) = fetched_transaction();
+        assert_eq!(tx.compute_tx_hash().unwrap(), tx.id);
+    }
+
+    #[test]
+    fn blockchain_info_should_be_deserialized_to_numbers() {
+        let info: BlockchainInfo = typed_fixture("get_blockchain_info");
+        assert_eq!(info.num_tx_blocks, 589778);
+        assert_eq!(info.num_dsblocks, 5899);
+        assert_eq!(info.num_transactions, 4350627);
+        assert_eq!(info.current_mini_epoch, 589778);
+        assert_eq!(info.current_dsepoch, 5898);
+        assert_eq!(info.num_txns_dsepoch, 3);
+        assert_eq!(info.num_txns_tx_epoch, 0);
+    }
+
+    #[test]
+    fn ds_block_should_be_deserialized_to_typed_fields() {
+        let block: DsBlock = typed_fixture("get_ds_block");
+        assert_eq!(block.header.block_num, 9000);
+        assert_eq!(block.header.gas_price, 2_000_000_000);
+        assert_eq!(block.header.timestamp, Timestamp::from_micros(1612477810679440));
+    }
+
+    #[test]
+    fn tx_block_should_be_deserialized_to_typed_fields() {
+        let block: TxBlock = typed_fixture("get_tx_block");
+        let header = block.header;
+        assert_eq!(header.block_num, 1002353);
+        assert_eq!(header.dsblock_num, 10024);
+        assert_eq!(header.gas_limit, 6500000);
+        assert_eq!(header.gas_used, 523);
+        assert_eq!(header.rewards, 0);
+        assert_eq!(header.txn_fees, 1_046_000_000_000);
+        assert_eq!(header.timestamp.as_micros(), 1612477810679440);
+        // Numbers and timestamps format back to the strings returned by the node.
+        assert_eq!(header.timestamp.to_string(), "1612477810679440");
+        assert_eq!(header.block_num.to_string(), "1002353");
+    }
+
+    #[test]
+    fn versions_and_addresses_should_not_format_to_the_node_strings() {
+        let tx: GetTransactionResponse = typed_fixture("get_transaction");
+        // The node returns "65537" and "2a5e5b0b5e3a4b7c2b3a4e6c43e7d29b5a6e6d0d", see the changelog.
+        assert_eq!(tx.version.to_string(), "chain_id: 1, msg_version: 1");
+        assert_eq!(tx.version.pack().to_string(), "65537");
+        assert_eq!(tx.to_addr.to_string(), "0x2A5E5B0B5E3A4B7C2B3a4E6c43E7d29b5a6e6d0D");
+        assert_eq!(
+            tx.to_addr.trim_start_matches("0x").to_lowercase(),
+            "2a5e5b0b5e3a4b7c2b3a4e6c43e7d29b5a6e6d0d"
+        );
+    }
+
+    #[test]
+    fn transaction_should_be_deserialized_to_typed_fields() {
+        let tx: GetTransactionResponse = typed_fixture("get_transaction");
+        assert_eq!(
+            tx.id,
+            "a583dfa35ddfc571ac817813839adee5bb98f80ad9bfa14895ff715803ac161b"
+                .parse::<TxHash>()
+                .unwrap()
+        );
+        assert_eq!(tx.version, Version::new(1));
+        assert_eq!(tx.nonce, 96);
+        assert_eq!(tx.to_addr, address("0x2a5e5b0b5e3a4b7c2b3a4e6c43e7d29b5a6e6d0d"));
+        assert_eq!(tx.amount, 0);
+        assert_eq!(tx.gas_price, 2_000_000_000);
+        assert_eq!(tx.gas_limit, 5000);
+
+        let receipt = tx.receipt;
+        assert_eq!(receipt.cumulative_gas, 523);
+        assert_eq!(receipt.epoch_num, 1002353);
+        let event = receipt.event_log("TransferSuccess").unwrap();
+        assert_eq!(event.address, address("0x2a5e5b0b5e3a4b7c2b3a4e6c43e7d29b5a6e6d0d"));
+        let transition = &receipt.transitions.unwrap()[0];
+        assert_eq!(transition.addr, address("0x2a5e5b0b5e3a4b7c2b3a4e6c43e7d29b5a6e6d0d"));
+        assert_eq!(transition.msg._amount, 0);
+        assert_eq!(
+            transition.msg._recipient,
+            address("0x9c6bde9a1e1a1d3f6b0a46e8fe0cb1d0db8b1f0a")
+        );
+    }
+
+    #[test]
+    fn fixture_transaction_should_round_trip_its_signature_and_hash() {
+        let tx: GetTransactionResponse = typed_fixture("get_transaction");
+        assert_eq!(
+            tx.verify_signature().unwrap(),
+            address("0x381f4008505e940ad7681ec3468a719060caf796")
+        );
+        assert_eq!(tx.compute_tx_hash().unwrap(), tx.id);
+    }
+
+    #[test]
+    fn transaction_should_be_deserialized_from_a_json_value() {
+        let value = fixture("get_transaction", &[]);
+        let tx: GetTransactionResponse = serde_json::from_value(value).unwrap();
+        assert_eq!(tx.to_addr, address("0x2a5e5b0b5e3a4b7c2b3a4e6c43e7d29b5a6e6d0d"));
+    }
+
+    #[test]
+    fn transaction_status_should_be_deserialized_to_typed_fields() {
+        let status: TransactionStatus = typed_fixture("get_transaction_status");
+        assert_eq!(status.amount, 0);
+        assert_eq!(status.epoch_inserted, 1002353);
+        assert_eq!(status.epoch_updated, 1002353);
+        assert_eq!(status.gas_limit, 5000);
+        assert_eq!(status.gas_price, 2_000_000_000);
+        assert_eq!(status.last_modified, Timestamp::from_micros(1612477810679440));
+        assert_eq!(status.nonce, 96);
+        assert_eq!(status.sender_addr, address("0x381f4008505e940ad7681ec3468a719060caf796"));
+        assert_eq!(status.to_addr, address("0x2a5e5b0b5e3a4b7c2b3a4e6c43e7d29b5a6e6d0d"));
+        assert_eq!(status.version, Version::new(1));
+        assert_eq!(status.status_code(), TxStatusCode::Confirmed);
+        assert_eq!(status.status_message(), "Confirmed");
+    }
+
+    #[test]
+    fn every_documented_status_code_should_be_decoded() {
+        use TxStatusCode::*;
+
+        // (code, status, message, is_pending, is_final)
+        let table = [
+            (0, NotFound, "Transaction not found", false, false),
+            (1, Dispatched, "Pending - Dispatched", true, false),
+            (
+                2,
+                SoftConfirmed,
+                "Pending - Soft-confirmed (awaiting Tx block generation)",
+                true,
+                false,
+            ),
+            (3, Confirmed, "Confirmed", false, true),
+            (4, NonceTooHigh, "Pending - Nonce is higher than expected", true, false),
+            (
+                5,
+                MicroblockGasLimitExceeded,
+                "Pending - Microblock gas limit exceeded",
+                true,
+                false,
+            ),
+            (6, ConsensusFailure, "Pending - Consensus failure in network", true, false),
+            (10, MathError, "Rejected - Transaction caused math error", false, true),
+            (11, ScillaInvocationError, "Rejected - Scilla invocation error", false, true),
+            (
+                12,
+                ContractInitError,
+                "Rejected - Contract account initialization error",
+                false,
+                true,
+            ),
+            (13, InvalidSourceAccount, "Rejected - Invalid source account", false, true),
+            (
+                14,
+                GasLimitHigherThanShardGasLimit,
+                "Rejected - Gas limit higher than shard gas limit",
+                false,
+                true,
+            ),
+            (15, UnknownTxType, "Rejected - Unknown transaction type", false, true),
+            (16, WrongShard, "Rejected - Transaction sent to wrong shard", false, true),
+            (
+                17,
+                CrossShardContractCall,
+                "Rejected - Contract & source account cross-shard issue",
+                false,
+                true,
+            ),
+            (18, CodeSizeExceeded, "Rejected - Code size exceeded limit", false, true),
+            (
+                19,
+                VerificationFailed,
+                "Rejected - Transaction verification failed",
+                false,
+                true,
+            ),
+            (20, GasLimitTooLow, "Rejected - Gas limit too low", false, true),
+            (21, InsufficientBalance, "Rejected - Insufficient balance", false, true),
+            (
+                22,
+                InsufficientGasForChecker,
+                "Rejected - Insufficient gas to invoke Scilla checker",
+                false,
+                true,
+            ),
+            (
+                23,
+                DuplicateTransaction,
+                "Rejected - Duplicate transaction exists",
+                false,
+                true,
+            ),
+            (
+                24,
+                HigherGasPriceWithSameNonce,
+                "Rejected - Transaction with same nonce but same/higher gas price exists",
+                false,
+                true,
+            ),
+            (
+                25,
+                InvalidDestinationAddress,
+                "Rejected - Invalid destination address",
+                false,
+                true,
+            ),
+            (
+                26,
+                FailedToAddContractAccount,
+                "Rejected - Failed to add contract account to state",
+                false,
+                true,
+            ),
+            (27, NonceTooLow, "Rejected - Nonce is lower than expected", false, true),
+            (255, InternalError, "Rejected - Internal error", false, true),
+        ];
+
+        for (code, status, message, is_pending, is_final) in table {
+            assert_eq!(TxStatusCode::from(code), status, "code {code}");
+            assert_eq!(status.code(), code, "{status:?}");
+            assert_eq!(status.message(), message, "{status:?}");
+            assert_eq!(status.to_string(), message, "{status:?}");
+            assert_eq!(status.is_pending(), is_pending, "{status:?}");
+            assert_eq!(status.is_final(), is_final, "{status:?}");
+            assert_eq!(status.is_rejected(), is_final && status != Confirmed, "{status:?}");
+        }
+    }
+
+    #[test]
+    fn every_receipt_error_code_should_be_decoded() {
+        use ReceiptError::*;
+
+        let table = [
+            (0, CheckerFailed, "CHECKER_FAILED"),
+            (1, RunnerFailed, "RUNNER_FAILED"),
+            (2, BalanceTransferFailed, "BALANCE_TRANSFER_FAILED"),
+            (3, ExecuteCmdFailed, "EXECUTE_CMD_FAILED"),
+            (4, ExecuteCmdTimeout, "EXECUTE_CMD_TIMEOUT"),
+            (5, NoGasRemainingFound, "NO_GAS_REMAINING_FOUND"),
+            (6, NoAcceptedFound, "NO_ACCEPTED_FOUND"),
+            (7, CallContractFailed, "CALL_CONTRACT_FAILED"),
+            (8, CreateContractFailed, "CREATE_CONTRACT_FAILED"),
+            (9, JsonOutputCorrupted, "JSON_OUTPUT_CORRUPTED"),
+            (10, ContractNotExist, "CONTRACT_NOT_EXIST"),
+            (11, StateCorrupted, "STATE_CORRUPTED"),
+            (12, LogEntryInstallFailed, "LOG_ENTRY_INSTALL_FAILED"),
+            (13, MessageCorrupted, "MESSAGE_CORRUPTED"),
+            (14, ReceiptIsNull, "RECEIPT_IS_NULL"),
+            (15, MaxEdgesReached, "MAX_EDGES_REACHED"),
+            (16, ChainCallDiffShard, "CHAIN_CALL_DIFF_SHARD"),
+            (17, PreparationFailed, "PREPARATION_FAILED"),
+            (18, NoOutput, "NO_OUTPUT"),
+            (19, OutputIllegal, "OUTPUT_ILLEGAL"),
+            (20, MapDepthMissing, "MAP_DEPTH_MISSING"),
+            (21, GasNotSufficient, "GAS_NOT_SUFFICIENT"),
+            (22, InternalError, "INTERNAL_ERROR"),
+            (23, LibraryAsRecipient, "LIBRARY_AS_RECIPIENT"),
+            (24, VersionInconsistent, "VERSION_INCONSISTENT"),
+            (25, LibraryExtractionFailed, "LIBRARY_EXTRACTION_FAILED"),
+        ];
+
+        for (code, error, name) in table {
+            assert_eq!(ReceiptError::from(code), error, "code {code}");
+            assert_eq!(error.code(), code, "{error:?}");
+            assert_eq!(error.name(), name, "{error:?}");
+            assert_eq!(error.to_string(), format!("{} ({name})", error.message()), "{error:?}");
+        }
+
+        assert_eq!(ReceiptError::from(26), Unknown(26));
+        assert_eq!(Unknown(26).code(), 26);
+        assert_eq!(Unknown(26).to_string(), "Unknown error (26)");
+    }
+
+    #[test]
+    fn failure_reason_should_combine_errors_and_exceptions() {
+        let receipt = receipt(json!({
+            "errors": { "0": [7], "1": [1, 21] },
+            "exceptions": [{ "line": 87, "message": "Exception thrown: (Message [(_exception : (String \"InsufficientFunds\"))])" }],
+            "success": false
+        }));
+
+        let reason = receipt.failure_reason().unwrap();
+        assert_eq!(reason.errors[&0], vec![ReceiptError::CallContractFailed]);
+        assert_eq!(
+            reason.errors[&1],
+            vec![ReceiptError::RunnerFailed, ReceiptError::GasNotSufficient]
+        );
+        assert_eq!(
+            reason.exceptions,
+            vec![ExceptionEntry {
+                line: 87,
+                message: "Exception thrown: (Message [(_exception : (String \"InsufficientFunds\"))])".to_string()
+            }]
+        );
+        assert_eq!(
+            reason.to_string(),
+            "depth 0: Contract call failed (CALL_CONTRACT_FAILED); \
+             depth 1: Scilla runner failed (RUNNER_FAILED), Insufficient gas (GAS_NOT_SUFFICIENT); \
+             line 87: Exception thrown: (Message [(_exception : (String \"InsufficientFunds\"))])"
+        );
+    }
+
+    #[test]
+    fn failure_reason_should_be_none_for_successful_transactions() {
+        let receipt = receipt(json!({}));
+
+        assert!(receipt.failure_reason().is_none());
+        assert!(receipt.receipt_errors().is_empty());
+    }
+
+    #[test]
+    fn undocumented_status_codes_should_be_kept() {
+        for code in [7, 9, 28, 254] {
+            let status = TxStatusCode::from(code);
+            assert_eq!(status, TxStatusCode::Unknown(code));
+            assert_eq!(status.code(), code);
+            assert_eq!(status.to_string(), format!("Unknown status ({code})"));
+            assert!(!status.is_pending());
+            assert!(!status.is_final());
+        }
+    }
+
+    #[test]
+    fn current_ds_comm_should_be_deserialized_to_numbers() {
+        let comm: GetCurrentDsCommResponse = typed_fixture("get_current_ds_comm");
+        assert_eq!(comm.current_ds_epoch, 5898);
+        assert_eq!(comm.current_tx_epoch, 589778);
+    }
+
+    #[test]
+    fn transactions_for_tx_block_should_be_deserialized_to_hashes() {
+        let transactions: TransactionsForTxBlockEx = typed_fixture("get_transactions_for_tx_block_ex");
+        let tx: GetTransactionResponse = typed_fixture("get_transaction");
+        assert!(transactions.transactions[0].is_empty());
+        assert_eq!(transactions.transactions[1], vec![tx.id]);
+    }
+}
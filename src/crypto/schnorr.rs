@@ -1,12 +1,12 @@
 use super::Signature;
 use crate::core::{PrivateKey, PublicKey};
 use k256::{
-    elliptic_curve::{ops::Reduce, sec1::ToEncodedPoint, Group},
-    AffinePoint, Scalar, U256,
+    elliptic_curve::{bigint::ArrayEncoding, ops::Reduce, sec1::ToEncodedPoint, Curve, Group, PrimeField},
+    AffinePoint, FieldBytes, Scalar, Secp256k1, U256,
 };
 use sha2::{Digest, Sha256};
 
-#[allow(unused)]
+/// Signs the message using a random nonce.
 pub fn sign(message: &[u8], secret_key: &PrivateKey) -> Signature {
     loop {
         let k = Scalar::generate_vartime(&mut rand::thread_rng());
@@ -17,6 +17,33 @@ pub fn sign(message: &[u8], secret_key: &PrivateKey) -> Signature {
     }
 }
 
+/// Signs the message using a nonce derived from the private key and the message, as described in RFC6979
+/// with HMAC-SHA256. Signing the same message with the same key always results in the same signature.
+pub fn sign_deterministic(message: &[u8], secret_key: &PrivateKey) -> Signature {
+    let h = <Scalar as Reduce<U256>>::reduce_bytes(&Sha256::digest(message)).to_bytes();
+    let x = secret_key.to_bytes();
+    let n = Secp256k1::ORDER.to_be_byte_array();
+
+    // The nonce is unusable with a negligible probability. In that case, the next one is derived by
+    // passing a counter as additional data.
+    let mut retry = 0u32;
+    loop {
+        let extra_data = if retry == 0 { vec![] } else { retry.to_be_bytes().to_vec() };
+        let k = deterministic_nonce(&x, &n, &h, &extra_data);
+        let k = Scalar::from_repr(k).expect("RFC6979 nonce is always less than the order");
+
+        if let Some(signature) = sign_inner(k, message, secret_key) {
+            return signature;
+        }
+        retry += 1;
+    }
+}
+
+/// Derives the nonce k of RFC6979 section 3.2 for the secret `x`, the curve order `n` and the reduced hash `h`.
+fn deterministic_nonce(x: &FieldBytes, n: &FieldBytes, h: &FieldBytes, extra_data: &[u8]) -> FieldBytes {
+    rfc6979::generate_k::<Sha256, _>(x, n, h, extra_data)
+}
+
 fn sign_inner(k: Scalar, message: &[u8], secret_key: &PrivateKey) -> Option<Signature> {
     let public_key = secret_key.public_key();
 
@@ -81,7 +108,7 @@ mod tests {
 
     use super::verify;
 
-    use super::sign_inner;
+    use super::{deterministic_nonce, sign_deterministic, sign_inner};
 
     #[test]
     fn signing() {
@@ -126,4 +153,55 @@ mod tests {
             assert!(verify(&message, &public_key, &signature).is_some());
         }
     }
+
+    #[test]
+    fn deterministic_nonce_should_match_rfc6979_vectors() {
+        // From RFC6979 A.2.5, P-256 with SHA-256. The derivation only depends on the order of the curve.
+        let n = "FFFFFFFF00000000FFFFFFFFFFFFFFFFBCE6FAADA7179E84F3B9CAC2FC632551";
+        let x = "C9AFA9D845BA75166B5C215767B1D6934E50C3DB36E89B127B8A622B120F6721";
+        let cases = [
+            (
+                "AF2BDBE1AA9B6EC1E2ADE1D694F41FC71A831D0268E9891562113D8A62ADD1BF", // SHA-256("sample")
+                "A6E3C57DD01ABE90086538398355DD4C3B17AA873382B0F24D6129493D8AAD60",
+            ),
+            (
+                "9F86D081884C7D659A2FEAA0C55AD015A3BF4F1B2B0B822CD15D6C15B0F00A08", // SHA-256("test")
+                "D16B6AE827F17175E040871A1C7EC3500192C4C92677336EC2537ACAEE0008E0",
+            ),
+        ];
+        let bytes = |value: &str| FieldBytes::clone_from_slice(&hex::decode(value).unwrap());
+
+        for (h, k) in cases {
+            assert_eq!(deterministic_nonce(&bytes(x), &bytes(n), &bytes(h), &[]), bytes(k));
+        }
+    }
+
+    #[test]
+    fn sign_deterministic_should_match_fixed_vectors() {
+        let secret_key = "d96e9eb5b782a80ea153c937fa83e5948485fbfc8b7e7c069d7b914dbc350aba"
+            .parse::<PrivateKey>()
+            .unwrap();
+        let cases = [
+            (
+                "",
+                "2855A6CE2E7C01AF083EF55606C2C0C1071893435DDD413D7A4112524DE4F748",
+                "85B26F42CB8A173A61C0C2D55A5575081EF7D82BBFF683CC4C7CB079776C6154",
+            ),
+            (
+                "11223344aabb",
+                "0B40E38F3355A703619984E2095A9579799092655E91DFB8D002260E96A28462",
+                "C318C716CE9F404829FF19842FCC4CC1B41AC058FFAF4599B06452E9E1172AEE",
+            ),
+        ];
+
+        for (message, r, s) in cases {
+            let message = hex::decode(message).unwrap();
+            let signature = sign_deterministic(&message, &secret_key);
+
+            assert_eq!(signature.r().to_string(), r);
+            assert_eq!(signature.s().to_string(), s);
+            assert_eq!(signature, sign_deterministic(&message, &secret_key));
+            assert!(verify(&message, &secret_key.public_key(), &signature).is_some());
+        }
+    }
 }
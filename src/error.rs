@@ -4,12 +4,18 @@ use std::num::ParseIntError;
 
 use thiserror::Error as ThisError;
 
-use crate::transaction::Version;
+use crate::{
+    core::{TxHash, TxStatusCode},
+    transaction::Version,
+};
 
 #[derive(Debug, ThisError)]
 pub enum Error {
-    #[error("Failed to confirm transaction after {0} tries.")]
-    UnableToConfirmTransaction(u32),
+    #[error("Transaction {0} isn't confirmed in time.")]
+    TransactionTimedOut(TxHash),
+
+    #[error("Transaction {0} is rejected: {1}.")]
+    TransactionRejected(TxHash, TxStatusCode),
 
     #[error("{0} is not a valid base-16 address")]
     InvalidAddress(String),
@@ -47,6 +53,12 @@ pub enum Error {
     #[error("Field {0} doesn't exist in the contract init.")]
     NoSuchFieldInContractInit(String),
 
+    #[error("Parameter {0} doesn't exist in the {1} event.")]
+    NoSuchParamInEvent(String, String),
+
+    #[error("Parameter {0} doesn't exist in the {1} transition.")]
+    NoSuchParamInTransition(String, String),
+
     #[error("Failed to parse scilla value {0} as {1} type")]
     FailedToParseScillaValue(String, String),
 
@@ -59,15 +71,48 @@ pub enum Error {
     #[error("Failed to get the parent directory of the given path")]
     FailedToGetTheParentDirectory,
 
+    #[error("Signature doesn't match the message and the public key")]
+    InvalidSignature,
+
+    #[error("Invalid block ID: {0}")]
+    InvalidBlockId(String),
+
+    #[error("Tx block {0} doesn't extend the last processed block, the chain is reorganized")]
+    ChainReorganized(u64),
+
+    #[error("Transaction is not signed")]
+    TransactionIsNotSigned,
+
+    #[error("Nonce of the transaction must be set to sign it offline")]
+    NonceIsNotSetForTransaction,
+
+    #[error("Mnemonic phrase is not set")]
+    MnemonicIsNotSet,
+
+    #[error("Can't derive {1} accounts from index {0}, account indices must be below 2^31")]
+    AccountIndexOutOfRange(u32, u32),
+
+    #[error("At least one account must be derived")]
+    NoAccountToDerive,
+
     #[error(transparent)]
     JsonRpcError(#[from] jsonrpsee::core::ClientError),
 
+    #[error(transparent)]
+    WsHandshakeError(#[from] jsonrpsee::client_transport::ws::WsHandshakeError),
+
+    #[error(transparent)]
+    WsError(#[from] jsonrpsee::client_transport::ws::WsError),
+
     #[error(transparent)]
     FromHexError(#[from] hex::FromHexError),
 
     #[error(transparent)]
     K256k1Error(#[from] k256::elliptic_curve::Error),
 
+    #[error(transparent)]
+    SignatureError(#[from] k256::ecdsa::Error),
+
     #[error(transparent)]
     UrlParseError(#[from] url::ParseError),
 
@@ -83,9 +128,18 @@ pub enum Error {
     #[error(transparent)]
     ParseIntError(#[from] ParseIntError),
 
+    #[error(transparent)]
+    ProtoDecodeError(#[from] prost::DecodeError),
+
     #[error(transparent)]
     KeystoreError(#[from] eth_keystore::KeystoreError),
 
+    #[error(transparent)]
+    MnemonicError(#[from] coins_bip39::MnemonicError),
+
+    #[error(transparent)]
+    Bip32Error(#[from] coins_bip32::Bip32Error),
+
     #[error(transparent)]
     RegexError(#[from] regex::Error),
 }
@@ -0,0 +1,122 @@
+use std::{
+    fmt::Debug,
+    path::PathBuf,
+    sync::{Arc, Mutex},
+};
+
+use async_trait::async_trait;
+use serde::{Deserialize, Serialize};
+
+use crate::Error;
+
+/// The last tx block processed by a [BlockFollower](super::BlockFollower).
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct Checkpoint {
+    /// Number of the block.
+    pub block_num: u64,
+    /// Hash of the block, used to detect if the chain is reorganized after resuming.
+    pub block_hash: String,
+}
+
+/// A storage for the checkpoint of a [BlockFollower](super::BlockFollower).
+#[async_trait]
+pub trait CheckpointStore: Send + Sync + Debug {
+    /// Loads the last saved checkpoint, if any.
+    async fn load(&self) -> Result<Option<Checkpoint>, Error>;
+
+    /// Saves the given checkpoint, replacing the previous one.
+    async fn save(&self, checkpoint: &Checkpoint) -> Result<(), Error>;
+}
+
+/// Keeps the checkpoint in memory. Clones share the same checkpoint.
+#[derive(Debug, Clone, Default)]
+pub struct MemoryCheckpointStore {
+    checkpoint: Arc<Mutex<Option<Checkpoint>>>,
+}
+
+impl MemoryCheckpointStore {
+    /// Creates a new store without any checkpoint.
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Returns the current checkpoint.
+    pub fn checkpoint(&self) -> Option<Checkpoint> {
+        self.checkpoint.lock().unwrap().clone()
+    }
+}
+
+#[async_trait]
+impl CheckpointStore for MemoryCheckpointStore {
+    async fn load(&self) -> Result<Option<Checkpoint>, Error> {
+        Ok(self.checkpoint())
+    }
+
+    async fn save(&self, checkpoint: &Checkpoint) -> Result<(), Error> {
+        *self.checkpoint.lock().unwrap() = Some(checkpoint.clone());
+        Ok(())
+    }
+}
+
+/// Keeps the checkpoint in a JSON file.
+///
+/// The file is replaced atomically, so a crash while saving leaves the previous checkpoint intact.
+#[derive(Debug, Clone)]
+pub struct FileCheckpointStore {
+    path: PathBuf,
+}
+
+impl FileCheckpointStore {
+    /// Creates a new store which keeps the checkpoint in the given file. The file is created on the first save.
+    pub fn new(path: impl Into<PathBuf>) -> Self {
+        Self { path: path.into() }
+    }
+}
+
+#[async_trait]
+impl CheckpointStore for FileCheckpointStore {
+    async fn load(&self) -> Result<Option<Checkpoint>, Error> {
+        match tokio::fs::read(&self.path).await {
+            Ok(content) => Ok(Some(serde_json::from_slice(&content)?)),
+            Err(e) if e.kind() == std::io::ErrorKind::NotFound => Ok(None),
+            Err(e) => Err(e.into()),
+        }
+    }
+
+    async fn save(&self, checkpoint: &Checkpoint) -> Result<(), Error> {
+        let temp_path = self.path.with_extension("tmp");
+        tokio::fs::write(&temp_path, serde_json::to_vec(checkpoint)?).await?;
+        tokio::fs::rename(&temp_path, &self.path).await?;
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::env;
+
+    use super::{Checkpoint, CheckpointStore, FileCheckpointStore};
+
+    #[tokio::test]
+    async fn file_store_should_keep_the_last_checkpoint() {
+        let path = env::temp_dir().join(format!("zilliqa-rs-checkpoint-{}.json", std::process::id()));
+        let _ = std::fs::remove_file(&path);
+
+        let store = FileCheckpointStore::new(&path);
+        assert_eq!(store.load().await.unwrap(), None);
+
+        for block_num in [10, 11] {
+            store
+                .save(&Checkpoint {
+                    block_num,
+                    block_hash: format!("{block_num:064x}"),
+                })
+                .await
+                .unwrap();
+        }
+
+        let checkpoint = FileCheckpointStore::new(&path).load().await.unwrap().unwrap();
+        assert_eq!(checkpoint.block_num, 11);
+        std::fs::remove_file(&path).unwrap();
+    }
+}
@@ -0,0 +1,590 @@
+/*!
+Follow the chain block by block
+
+[BlockFollower] walks through tx blocks in order, fetches their transactions and hands the ones matching a
+[TxFilter] over to the application. The last processed block is persisted through a [CheckpointStore], so
+a service can resume where it stopped after a restart and every block is processed exactly once.
+
+# Example
+```no_run
+use zilliqa_rs::follower::{BlockFollower, FileCheckpointStore, TxFilter};
+use zilliqa_rs::providers::{Http, Provider};
+
+#[tokio::main]
+async fn main() -> anyhow::Result<()> {
+    let provider = Provider::<Http>::try_from("https://api.zilliqa.com")?;
+    let contract = "0x2a5e5b0b5e3a4b7c2b3a4e6c43e7d29b5a6e6d0d".parse()?;
+
+    let mut follower = BlockFollower::new(&provider, FileCheckpointStore::new("checkpoint.json"))
+        .with_filter(TxFilter::new().contract(contract).event("TransferSuccess"));
+
+    follower
+        .run(|block| async move {
+            for tx in block.transactions {
+                println!("{} sent {}", tx.sender, tx.transaction.id);
+            }
+            Ok(())
+        })
+        .await?;
+    Ok(())
+}
+```
+*/
+
+pub mod checkpoint;
+
+use std::{future::Future, time::Duration};
+
+pub use checkpoint::{Checkpoint, CheckpointStore, FileCheckpointStore, MemoryCheckpointStore};
+use futures::{StreamExt, TryStreamExt};
+
+use crate::{
+    core::{BlockId, GetTransactionResponse, PublicKey, TxBlock, TxHash, ZilAddress},
+    middlewares::Middleware,
+    providers::{stream, PubsubClient, SubscriptionStream},
+    Error,
+};
+
+/// Selects the transactions a [BlockFollower] emits.
+///
+/// Every criterion accepts any of the values given for it, and a transaction must match all of the
+/// criteria which are set. An empty filter matches every transaction.
+#[derive(Debug, Clone, Default)]
+pub struct TxFilter {
+    senders: Vec<ZilAddress>,
+    recipients: Vec<ZilAddress>,
+    contracts: Vec<ZilAddress>,
+    events: Vec<String>,
+}
+
+impl TxFilter {
+    /// Creates a filter which matches every transaction.
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Matches transactions sent by the given address.
+    pub fn sender(mut self, address: ZilAddress) -> Self {
+        self.senders.push(address);
+        self
+    }
+
+    /// Matches transactions sent to the given address.
+    pub fn recipient(mut self, address: ZilAddress) -> Self {
+        self.recipients.push(address);
+        self
+    }
+
+    /// Matches transactions touching the given contract, i.e. calling it, calling it through a
+    /// transition of another contract, or making it emit an event.
+    pub fn contract(mut self, address: ZilAddress) -> Self {
+        self.contracts.push(address);
+        self
+    }
+
+    /// Matches transactions emitting an event with the given name.
+    pub fn event(mut self, event_name: impl Into<String>) -> Self {
+        self.events.push(event_name.into());
+        self
+    }
+
+    /// Checks if the transaction, sent by `sender`, matches the filter.
+    pub fn matches(&self, sender: &ZilAddress, tx: &GetTransactionResponse) -> bool {
+        let receipt = &tx.receipt;
+        let event_logs = receipt.event_logs.as_deref().unwrap_or_default();
+        let transitions = receipt.transitions.as_deref().unwrap_or_default();
+
+        let touches = |contract: &ZilAddress| {
+            &tx.to_addr == contract
+                || event_logs.iter().any(|event| &event.address == contract)
+                || transitions
+                    .iter()
+                    .any(|transition| &transition.addr == contract || &transition.msg._recipient == contract)
+        };
+
+        (self.senders.is_empty() || self.senders.contains(sender))
+            && (self.recipients.is_empty() || self.recipients.contains(&tx.to_addr))
+            && (self.contracts.is_empty() || self.contracts.iter().any(touches))
+            && (self.events.is_empty() || event_logs.iter().any(|event| self.events.contains(&event._eventname)))
+    }
+}
+
+/// A transaction emitted by a [BlockFollower].
+#[derive(Debug, Clone)]
+pub struct FollowedTransaction {
+    /// Address of the sender, derived from its public key.
+    pub sender: ZilAddress,
+    /// The transaction, as returned by the node.
+    pub transaction: GetTransactionResponse,
+}
+
+/// A tx block and its transactions matching the filter of a [BlockFollower].
+#[derive(Debug, Clone)]
+pub struct FollowedBlock {
+    /// Number of the block.
+    pub block_num: u64,
+    /// Hash of the block.
+    pub block_hash: String,
+    /// Transactions of the block matching the filter, in the order of the block.
+    pub transactions: Vec<FollowedTransaction>,
+    /// Transactions of the block which are skipped because their sender public key is invalid, so they
+    /// can't be matched against the filter.
+    pub invalid_transactions: Vec<TxHash>,
+}
+
+impl FollowedBlock {
+    /// The checkpoint to save once the block is processed.
+    pub fn checkpoint(&self) -> Checkpoint {
+        Checkpoint {
+            block_num: self.block_num,
+            block_hash: self.block_hash.clone(),
+        }
+    }
+}
+
+/// Follows tx blocks in order and emits the transactions matching a [TxFilter].
+///
+/// [BlockFollower::next_block] returns the block after the last committed one, and [BlockFollower::commit]
+/// saves the block as the checkpoint. A block is returned again until it's committed, so a service which
+/// commits a block after processing it, never skips or repeats a block, even if it restarts in between.
+///
+/// Every block must extend the previous one, otherwise [Error::ChainReorganized] is returned. Then
+/// [BlockFollower::rewind] moves the checkpoint back to a block of the new chain, and following resumes from
+/// there. To stay away from the tip of the chain, blocks can be processed only after a number of confirmations.
+///
+/// Requests go through the given middleware, so e.g. a [RetryMiddleware](crate::middlewares::retry::RetryMiddleware)
+/// retries them.
+#[derive(Debug)]
+pub struct BlockFollower<'a, M> {
+    middleware: &'a M,
+    store: Box<dyn CheckpointStore>,
+    filter: TxFilter,
+    start_block: Option<u64>,
+    confirmations: u64,
+    poll_interval: Duration,
+    last: Option<Checkpoint>,
+    loaded: bool,
+    new_blocks: Option<SubscriptionStream<TxBlock>>,
+}
+
+impl<'a, M: Middleware> BlockFollower<'a, M> {
+    /// Creates a new follower which persists its checkpoint in the given store.
+    ///
+    /// Without a checkpoint, it starts from the latest block. Use [BlockFollower::from_block] to start from
+    /// another block.
+    pub fn new(middleware: &'a M, store: impl CheckpointStore + 'static) -> Self {
+        Self {
+            middleware,
+            store: Box::new(store),
+            filter: TxFilter::default(),
+            start_block: None,
+            confirmations: 0,
+            poll_interval: Duration::from_secs(10),
+            last: None,
+            loaded: false,
+            new_blocks: None,
+        }
+    }
+
+    /// Emits only the transactions matching the given filter.
+    pub fn with_filter(mut self, filter: TxFilter) -> Self {
+        self.filter = filter;
+        self
+    }
+
+    /// Starts from the given block if there is no checkpoint.
+    pub fn from_block(mut self, block_num: u64) -> Self {
+        self.start_block = Some(block_num);
+        self
+    }
+
+    /// Processes a block only when the given number of blocks are mined on top of it.
+    pub fn with_confirmations(mut self, confirmations: u64) -> Self {
+        self.confirmations = confirmations;
+        self
+    }
+
+    /// Sets how often the node is asked for new blocks. It's 10 seconds by default.
+    pub fn with_poll_interval(mut self, poll_interval: Duration) -> Self {
+        self.poll_interval = poll_interval;
+        self
+    }
+
+    /// The last committed block, if any.
+    pub fn checkpoint(&self) -> Option<&Checkpoint> {
+        self.last.as_ref()
+    }
+
+    /// Waits for the block after the last committed one, and returns its transactions matching the filter.
+    pub async fn next_block(&mut self) -> Result<FollowedBlock, Error> {
+        let block_num = self.next_block_num().await?;
+        while self.latest_confirmed_block().await? < Some(block_num) {
+            self.wait_for_new_block().await;
+        }
+
+        let block = self.middleware.get_tx_block(BlockId::Number(block_num)).await?;
+        if let Some(last) = &self.last {
+            if !same_hash(&block.header.prev_block_hash, &last.block_hash) {
+                return Err(Error::ChainReorganized(block_num));
+            }
+        }
+
+        let mut transactions = vec![];
+        let mut invalid_transactions = vec![];
+        let mut bodies = stream::block_txn_bodies(
+            self.middleware,
+            block_num,
+            block.header.num_txns,
+            self.middleware.provider().max_concurrent_requests(),
+        );
+        while let Some(tx) = bodies.try_next().await? {
+            let sender = match tx.sender_pub_key.parse::<PublicKey>() {
+                Ok(public_key) => ZilAddress::try_from(&public_key)?,
+                Err(_) => {
+                    invalid_transactions.push(tx.id);
+                    continue;
+                }
+            };
+            if self.filter.matches(&sender, &tx) {
+                transactions.push(FollowedTransaction { sender, transaction: tx });
+            }
+        }
+
+        Ok(FollowedBlock {
+            block_num,
+            block_hash: block.body.block_hash,
+            transactions,
+            invalid_transactions,
+        })
+    }
+
+    /// Marks the block as processed and saves it as the checkpoint.
+    pub async fn commit(&mut self, block: &FollowedBlock) -> Result<(), Error> {
+        let checkpoint = block.checkpoint();
+        self.store.save(&checkpoint).await?;
+        self.last = Some(checkpoint);
+        Ok(())
+    }
+
+    /// Moves the checkpoint `n` blocks back, at most to block 0, and saves it. The hash of the new checkpoint is
+    /// taken from the current chain.
+    ///
+    /// It's meant to recover from [Error::ChainReorganized]: the last `n` committed blocks are returned again,
+    /// as they are in the new chain, so `n` must reach back to a block both chains share. The application is
+    /// responsible for undoing what it did with the replaced blocks. Without a checkpoint, it does nothing.
+    pub async fn rewind(&mut self, n: u64) -> Result<(), Error> {
+        self.load_checkpoint().await?;
+        let Some(last) = &self.last else {
+            return Ok(());
+        };
+
+        let block_num = last.block_num.saturating_sub(n);
+        let block = self.middleware.get_tx_block(BlockId::Number(block_num)).await?;
+        let checkpoint = Checkpoint {
+            block_num,
+            block_hash: block.body.block_hash,
+        };
+        self.store.save(&checkpoint).await?;
+        self.last = Some(checkpoint);
+        Ok(())
+    }
+
+    /// Passes blocks to the handler one by one, forever, and commits every block the handler processes
+    /// successfully. It returns the first error, of either the follower or the handler.
+    pub async fn run<F, Fut>(&mut self, mut handler: F) -> Result<(), Error>
+    where
+        F: FnMut(FollowedBlock) -> Fut,
+        Fut: Future<Output = Result<(), Error>>,
+    {
+        loop {
+            let block = self.next_block().await?;
+            let checkpoint = block.checkpoint();
+            handler(block).await?;
+            self.store.save(&checkpoint).await?;
+            self.last = Some(checkpoint);
+        }
+    }
+
+    async fn load_checkpoint(&mut self) -> Result<(), Error> {
+        if !self.loaded {
+            self.last = self.store.load().await?;
+            self.loaded = true;
+        }
+        Ok(())
+    }
+
+    async fn next_block_num(&mut self) -> Result<u64, Error> {
+        self.load_checkpoint().await?;
+        if let Some(last) = &self.last {
+            return Ok(last.block_num + 1);
+        }
+
+        match self.start_block {
+            Some(start_block) => Ok(start_block),
+            None => {
+                // Without any checkpoint, the follower starts from the latest confirmed block.
+                let latest = self.latest_confirmed_block().await?.unwrap_or_default();
+                self.start_block = Some(latest);
+                Ok(latest)
+            }
+        }
+    }
+
+    async fn latest_confirmed_block(&self) -> Result<Option<u64>, Error> {
+        let num_tx_blocks = self.middleware.get_num_tx_blocks().await?;
+        Ok(num_tx_blocks.checked_sub(1 + self.confirmations))
+    }
+
+    async fn wait_for_new_block(&mut self) {
+        match &mut self.new_blocks {
+            Some(new_blocks) => {
+                if let Ok(None) = tokio::time::timeout(self.poll_interval, new_blocks.next()).await {
+                    // The subscription is closed, fall back to polling.
+                    self.new_blocks = None;
+                }
+            }
+            None => tokio::time::sleep(self.poll_interval).await,
+        }
+    }
+}
+
+impl<'a, M> BlockFollower<'a, M>
+where
+    M: Middleware,
+    M::Provider: PubsubClient,
+{
+    /// Waits for new blocks using a websocket subscription instead of polling the node.
+    pub async fn with_new_block_subscription(mut self) -> Result<Self, Error> {
+        self.new_blocks = Some(self.middleware.provider().subscribe_new_blocks().await?);
+        Ok(self)
+    }
+}
+
+fn same_hash(a: &str, b: &str) -> bool {
+    a.trim_start_matches("0x").eq_ignore_ascii_case(b.trim_start_matches("0x"))
+}
+
+#[cfg(test)]
+mod tests {
+    use std::time::Duration;
+
+    use claim::assert_matches;
+    use jsonrpsee::core::ClientError;
+    use serde_json::{json, Value};
+
+    use crate::{
+        core::{GetTransactionResponse, ZilAddress},
+        middlewares::retry::{RetryMiddleware, RetryPolicy},
+        providers::{
+            mock::{fixture, MockClient},
+            Provider,
+        },
+        Error,
+    };
+
+    use super::{BlockFollower, Checkpoint, CheckpointStore, MemoryCheckpointStore, TxFilter};
+
+    const CONTRACT: &str = "0x2a5e5b0b5e3a4b7c2b3a4e6c43e7d29b5a6e6d0d";
+
+    fn hash(n: u64) -> String {
+        format!("{n:064x}")
+    }
+
+    fn tx_block(block_num: u64, num_txns: u32) -> Value {
+        fixture(
+            "get_tx_block",
+            &[
+                ("/header/BlockNum", json!(block_num.to_string())),
+                ("/header/NumTxns", json!(num_txns)),
+                ("/header/PrevBlockHash", json!(hash(block_num - 1))),
+                ("/body/BlockHash", json!(hash(block_num))),
+            ],
+        )
+    }
+
+    fn transfer_to_contract() -> Value {
+        fixture("get_transaction", &[])
+    }
+
+    fn plain_transfer() -> Value {
+        let mut tx = transfer_to_contract();
+        tx["toAddr"] = json!("381f4008505e940ad7681ec3468a719060caf796");
+        tx["receipt"] = json!({"cumulative_gas": "50", "epoch_num": "1", "success": true});
+        tx
+    }
+
+    fn page(transactions: Vec<Value>) -> Value {
+        json!({"CurrPage": 0, "NumPages": 1, "Transactions": transactions})
+    }
+
+    fn follower(mock: &MockClient, store: MemoryCheckpointStore) -> (Provider<MockClient>, TxFilter, MemoryCheckpointStore) {
+        let filter = TxFilter::new().contract(CONTRACT.parse().unwrap());
+        (Provider::new(mock.clone(), 222), filter, store)
+    }
+
+    #[tokio::test]
+    async fn follower_should_emit_matching_transactions_and_commit_checkpoints() {
+        let mock = MockClient::default();
+        mock.push("12");
+        mock.push(tx_block(10, 2));
+        mock.push(page(vec![plain_transfer(), transfer_to_contract()]));
+        mock.push("12");
+        mock.push(tx_block(11, 0));
+
+        let (provider, filter, store) = follower(&mock, MemoryCheckpointStore::new());
+        let mut follower = BlockFollower::new(&provider, store.clone())
+            .with_filter(filter)
+            .from_block(10);
+
+        let block = follower.next_block().await.unwrap();
+        assert_eq!(block.block_num, 10);
+        assert_eq!(block.transactions.len(), 1);
+        assert_eq!(
+            block.transactions[0].sender,
+            "0x381f4008505e940ad7681ec3468a719060caf796".parse::<ZilAddress>().unwrap()
+        );
+        assert_eq!(store.checkpoint(), None);
+
+        follower.commit(&block).await.unwrap();
+        assert_eq!(store.checkpoint().unwrap().block_num, 10);
+
+        let block = follower.next_block().await.unwrap();
+        assert_eq!(block.block_num, 11);
+        assert!(block.transactions.is_empty());
+    }
+
+    #[tokio::test]
+    async fn follower_should_resume_from_the_checkpoint() {
+        let mock = MockClient::default();
+        mock.push("30");
+        mock.push(tx_block(21, 0));
+
+        let store = MemoryCheckpointStore::new();
+        store
+            .save(&Checkpoint {
+                block_num: 20,
+                block_hash: hash(20),
+            })
+            .await
+            .unwrap();
+
+        let (provider, filter, store) = follower(&mock, store);
+        let mut follower = BlockFollower::new(&provider, store).with_filter(filter).from_block(0);
+
+        assert_eq!(follower.next_block().await.unwrap().block_num, 21);
+    }
+
+    #[tokio::test]
+    async fn follower_should_wait_for_confirmations() {
+        let mock = MockClient::default();
+        mock.push("12");
+        mock.push("13");
+        mock.push(tx_block(11, 0));
+
+        let (provider, filter, store) = follower(&mock, MemoryCheckpointStore::new());
+        let mut follower = BlockFollower::new(&provider, store)
+            .with_filter(filter)
+            .from_block(11)
+            .with_confirmations(1)
+            .with_poll_interval(Duration::from_millis(1));
+
+        assert_eq!(follower.next_block().await.unwrap().block_num, 11);
+        let polls = mock
+            .requests()
+            .iter()
+            .filter(|(method, _)| method == "GetNumTxBlocks")
+            .count();
+        assert_eq!(polls, 2);
+    }
+
+    #[tokio::test]
+    async fn follower_should_detect_reorganized_chain_and_resume_after_rewinding() {
+        let mock = MockClient::default();
+        let mut block = tx_block(21, 0);
+        block["header"]["PrevBlockHash"] = json!(hash(99));
+        mock.push("30");
+        mock.push(block);
+        mock.push(tx_block(18, 0));
+        mock.push("30");
+        mock.push(tx_block(19, 0));
+
+        let store = MemoryCheckpointStore::new();
+        store
+            .save(&Checkpoint {
+                block_num: 20,
+                block_hash: hash(20),
+            })
+            .await
+            .unwrap();
+
+        let (provider, filter, store) = follower(&mock, store);
+        let mut follower = BlockFollower::new(&provider, store.clone()).with_filter(filter);
+
+        assert_matches!(follower.next_block().await, Err(Error::ChainReorganized(21)));
+        follower.rewind(2).await.unwrap();
+        assert_eq!(
+            store.checkpoint(),
+            Some(Checkpoint {
+                block_num: 18,
+                block_hash: hash(18),
+            })
+        );
+        assert_eq!(follower.next_block().await.unwrap().block_num, 19);
+    }
+
+    #[tokio::test]
+    async fn follower_should_skip_transactions_with_an_invalid_public_key() {
+        let mock = MockClient::default();
+        let mut invalid = transfer_to_contract();
+        invalid["ID"] = json!(hash(1));
+        invalid["senderPubKey"] = json!("0x00");
+        mock.push("12");
+        mock.push(tx_block(10, 2));
+        mock.push(page(vec![invalid, transfer_to_contract()]));
+
+        let (provider, filter, store) = follower(&mock, MemoryCheckpointStore::new());
+        let mut follower = BlockFollower::new(&provider, store).with_filter(filter).from_block(10);
+
+        let block = follower.next_block().await.unwrap();
+        assert_eq!(block.transactions.len(), 1);
+        assert_eq!(block.invalid_transactions, vec![hash(1).parse().unwrap()]);
+    }
+
+    #[tokio::test]
+    async fn follower_should_send_requests_through_the_middleware() {
+        let mock = MockClient::default();
+        mock.push_error(ClientError::RequestTimeout);
+        mock.push("12");
+        mock.push(tx_block(10, 0));
+
+        let (provider, filter, store) = follower(&mock, MemoryCheckpointStore::new());
+        let middleware = RetryMiddleware::new(provider).with_policy(RetryPolicy {
+            max_retries: 1,
+            initial_backoff: Duration::from_millis(1),
+            max_backoff: Duration::from_millis(1),
+            jitter: false,
+        });
+        let mut follower = BlockFollower::new(&middleware, store).with_filter(filter).from_block(10);
+
+        assert_eq!(follower.next_block().await.unwrap().block_num, 10);
+    }
+
+    #[test]
+    fn filter_should_require_all_criteria() {
+        let tx: GetTransactionResponse = serde_json::from_value(transfer_to_contract()).unwrap();
+        let sender: ZilAddress = "0x381f4008505e940ad7681ec3468a719060caf796".parse().unwrap();
+        let contract: ZilAddress = CONTRACT.parse().unwrap();
+
+        assert!(TxFilter::new().matches(&sender, &tx));
+        assert!(TxFilter::new()
+            .contract(contract.clone())
+            .event("TransferSuccess")
+            .matches(&sender, &tx));
+        assert!(TxFilter::new()
+            .recipient("0x9c6bde9a1e1a1d3f6b0a46e8fe0cb1d0db8b1f0a".parse().unwrap())
+            .recipient(contract.clone())
+            .matches(&sender, &tx));
+        assert!(!TxFilter::new().contract(contract).event("Minted").matches(&sender, &tx));
+        assert!(!TxFilter::new().sender(CONTRACT.parse().unwrap()).matches(&sender, &tx));
+    }
+}
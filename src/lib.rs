@@ -41,15 +41,26 @@ async fn main() -> anyhow::Result<()> {
 ```
 */
 
+extern crate self as zilliqa_rs;
+
 pub mod contract;
 pub mod core;
 pub mod crypto;
 pub mod error;
+pub mod follower;
 pub mod middlewares;
 pub mod providers;
 pub mod signers;
 pub mod transaction;
 pub use error::Error;
+pub use primitive_types;
+
+/// Re-exports used by the code generated by `scilla_contract!`. Not part of the public API.
+#[doc(hidden)]
+pub mod __private {
+    pub use serde;
+    pub use serde_json;
+}
 
 /// Run them with `cargo test --doc`
 #[doc = include_str!("../README.md")]
@@ -0,0 +1,256 @@
+use std::{
+    fmt::Debug,
+    sync::Mutex,
+    time::{Duration, Instant},
+};
+
+use async_trait::async_trait;
+use serde::de::DeserializeOwned;
+
+use crate::{core::CreateTransactionRequest, Error};
+
+use super::Middleware;
+
+/// A source of gas prices, in Qa.
+#[async_trait]
+pub trait GasOracle: Send + Sync + Debug {
+    async fn gas_price(&self) -> Result<u128, Error>;
+}
+
+#[derive(Debug)]
+enum GasPriceSource {
+    /// `GetMinimumGasPrice` of the network multiplied by the given factor.
+    Network {
+        multiplier: f64,
+    },
+    Oracle(Box<dyn GasOracle>),
+}
+
+/// A middleware that fills the gas price of transactions which don't have one.
+///
+/// By default, the gas price is the minimum gas price of the network, fetched using `GetMinimumGasPrice`.
+/// It can be scaled by a multiplier, or replaced entirely by a custom [GasOracle]. Gas prices are cached
+/// for a minute by default, so the price isn't fetched for every transaction.
+///
+/// The gas price of a transaction is considered unset if it's zero. Transactions without a gas price which
+/// don't go through this middleware are sent with the minimum gas price of the network.
+///
+/// # Example
+/// ```
+/// use std::time::Duration;
+/// use zilliqa_rs::middlewares::{gas_oracle::GasOracleMiddleware, signer::SignerMiddleware};
+/// use zilliqa_rs::providers::{Http, Provider};
+/// use zilliqa_rs::signers::LocalWallet;
+///
+/// #[tokio::main]
+/// async fn main() -> anyhow::Result<()> {
+///     let wallet = LocalWallet::create_random()?;
+///     let provider = Provider::<Http>::try_from("http://127.0.0.1:5555")?.with_chain_id(222);
+///     let provider = GasOracleMiddleware::new(SignerMiddleware::new(provider, wallet))
+///         .with_multiplier(1.2)
+///         .with_ttl(Duration::from_secs(30));
+///     Ok(())
+/// }
+/// ```
+#[derive(Debug)]
+pub struct GasOracleMiddleware<M> {
+    inner: M,
+    source: GasPriceSource,
+    ttl: Duration,
+    cache: Mutex<Option<(Instant, u128)>>,
+}
+
+impl<M: Middleware> GasOracleMiddleware<M> {
+    /// Creates a new GasOracleMiddleware which uses the minimum gas price of the network.
+    pub fn new(inner: M) -> Self {
+        Self {
+            inner,
+            source: GasPriceSource::Network { multiplier: 1.0 },
+            ttl: Duration::from_secs(60),
+            cache: Mutex::new(None),
+        }
+    }
+
+    /// Uses the minimum gas price of the network multiplied by the given factor.
+    pub fn with_multiplier(mut self, multiplier: f64) -> Self {
+        self.source = GasPriceSource::Network { multiplier };
+        self
+    }
+
+    /// Uses the given oracle instead of the minimum gas price of the network.
+    pub fn with_oracle(mut self, oracle: impl GasOracle + 'static) -> Self {
+        self.source = GasPriceSource::Oracle(Box::new(oracle));
+        self
+    }
+
+    /// Sets how long a fetched gas price is reused. A zero TTL disables caching.
+    pub fn with_ttl(mut self, ttl: Duration) -> Self {
+        self.ttl = ttl;
+        self
+    }
+
+    /// Returns the gas price to use for transactions, fetching it if the cached one is expired.
+    pub async fn gas_price(&self) -> Result<u128, Error> {
+        if let Some((fetched_at, gas_price)) = *self.cache.lock().unwrap() {
+            if fetched_at.elapsed() < self.ttl {
+                return Ok(gas_price);
+            }
+        }
+
+        let gas_price = match &self.source {
+            GasPriceSource::Network { multiplier } => {
+                let minimum = self.inner.get_minimum_gas_price().await?;
+                (minimum as f64 * multiplier).ceil() as u128
+            }
+            GasPriceSource::Oracle(oracle) => oracle.gas_price().await?,
+        };
+
+        *self.cache.lock().unwrap() = Some((Instant::now(), gas_price));
+        Ok(gas_price)
+    }
+}
+
+#[async_trait]
+impl<M: Middleware> Middleware for GasOracleMiddleware<M> {
+    type Provider = M::Provider;
+
+    type Inner = M;
+
+    fn inner(&self) -> &Self::Inner {
+        &self.inner
+    }
+
+    async fn send_transaction_without_confirm<T: Send + DeserializeOwned>(
+        &self,
+        mut tx: CreateTransactionRequest,
+    ) -> Result<T, Error> {
+        if tx.gas_price == u128::default() {
+            tx.gas_price = self.gas_price().await?;
+        }
+
+        self.inner.send_transaction_without_confirm(tx).await
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::time::Duration;
+
+    use async_trait::async_trait;
+    use serde_json::{json, Value};
+
+    use crate::{
+        core::CreateTransactionRequest,
+        middlewares::{signer::SignerMiddleware, Middleware},
+        providers::{mock::MockClient, Provider},
+        signers::LocalWallet,
+        transaction::TransactionBuilder,
+        Error,
+    };
+
+    use super::{GasOracle, GasOracleMiddleware};
+
+    fn gas_oracle(mock: &MockClient) -> GasOracleMiddleware<SignerMiddleware<Provider<MockClient>, LocalWallet>> {
+        let wallet = LocalWallet::create_random().unwrap();
+        GasOracleMiddleware::new(SignerMiddleware::new(Provider::new(mock.clone(), 222), wallet))
+    }
+
+    fn transfer() -> CreateTransactionRequest {
+        CreateTransactionRequest {
+            nonce: 1,
+            to_addr: "0x381f4008505e940ad7681ec3468a719060caf796".parse().unwrap(),
+            amount: 1,
+            gas_limit: 50,
+            ..Default::default()
+        }
+    }
+
+    fn tx_response() -> Value {
+        json!({"TranID": "bdadfd994f452df803cc223d1f417b02830ac96dbe5edad1b9f8d58613f95206", "Info": ""})
+    }
+
+    fn sent_gas_prices(mock: &MockClient) -> Vec<String> {
+        mock.requests()
+            .into_iter()
+            .filter(|(method, _)| method == "CreateTransaction")
+            .map(|(_, params)| params[0]["gasPrice"].as_str().unwrap().to_string())
+            .collect()
+    }
+
+    #[tokio::test]
+    async fn unset_gas_price_should_be_filled_from_cached_minimum_gas_price() {
+        let mock = MockClient::default();
+        mock.push("2000000000");
+        mock.push(tx_response());
+        mock.push(tx_response());
+
+        let provider = gas_oracle(&mock).with_multiplier(1.5);
+        provider.send_transaction_without_confirm::<Value>(transfer()).await.unwrap();
+        provider.send_transaction_without_confirm::<Value>(transfer()).await.unwrap();
+
+        assert_eq!(sent_gas_prices(&mock), vec!["3000000000", "3000000000"]);
+        assert_eq!(mock.requests().iter().filter(|(m, _)| m == "GetMinimumGasPrice").count(), 1);
+    }
+
+    #[tokio::test]
+    async fn expired_gas_price_should_be_fetched_again() {
+        let mock = MockClient::default();
+        mock.push("2000000000");
+        mock.push("4000000000");
+
+        let provider = gas_oracle(&mock).with_ttl(Duration::ZERO);
+        assert_eq!(provider.gas_price().await.unwrap(), 2000000000);
+        assert_eq!(provider.gas_price().await.unwrap(), 4000000000);
+    }
+
+    #[tokio::test]
+    async fn explicit_gas_price_should_be_left_untouched() {
+        let mock = MockClient::default();
+        mock.push(tx_response());
+
+        let provider = gas_oracle(&mock);
+        let tx = CreateTransactionRequest {
+            gas_price: 2100000000,
+            ..transfer()
+        };
+        provider.send_transaction_without_confirm::<Value>(tx).await.unwrap();
+
+        assert_eq!(sent_gas_prices(&mock), vec!["2100000000"]);
+    }
+
+    #[derive(Debug)]
+    struct FixedOracle(u128);
+
+    #[async_trait]
+    impl GasOracle for FixedOracle {
+        async fn gas_price(&self) -> Result<u128, Error> {
+            Ok(self.0)
+        }
+    }
+
+    #[tokio::test]
+    async fn custom_oracle_should_be_used_if_given() {
+        let mock = MockClient::default();
+        mock.push(tx_response());
+
+        let provider = gas_oracle(&mock).with_oracle(FixedOracle(2500000000));
+        provider.send_transaction_without_confirm::<Value>(transfer()).await.unwrap();
+
+        assert_eq!(sent_gas_prices(&mock), vec!["2500000000"]);
+    }
+
+    #[tokio::test]
+    async fn pay_transactions_should_get_their_gas_price_from_the_oracle() {
+        let mock = MockClient::default();
+        mock.push(tx_response());
+
+        let provider = gas_oracle(&mock).with_oracle(FixedOracle(2500000000));
+        let tx = TransactionBuilder::default()
+            .pay(1, "0x381f4008505e940ad7681ec3468a719060caf796".parse().unwrap())
+            .nonce(1)
+            .build();
+        provider.send_transaction_without_confirm::<Value>(tx).await.unwrap();
+
+        assert_eq!(sent_gas_prices(&mock), vec!["2500000000"]);
+    }
+}
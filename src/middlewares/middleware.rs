@@ -1,13 +1,15 @@
 use crate::{
     contract::ScillaVariable,
-    core::{types::*, TxHash, ZilAddress},
+    core::{types::*, BlockId, TxHash, ZilAddress},
     crypto::Signature,
-    providers::{JsonRpcClient, Provider},
-    transaction::Transaction,
+    providers::{stream, JsonRpcClient, Provider},
+    transaction::PendingTransaction,
     Error,
 };
 use async_trait::async_trait;
+use futures::stream::BoxStream;
 use serde::de::DeserializeOwned;
+use std::ops::Range;
 
 #[async_trait]
 pub trait Middleware: Sync + Send + std::fmt::Debug {
@@ -35,10 +37,13 @@ pub trait Middleware: Sync + Send + std::fmt::Debug {
     }
 
     /// Sends a transaction and returns a more higher-level response to work with on application layer.
-    async fn send_transaction(&self, tx: CreateTransactionRequest) -> Result<Transaction<Self::Provider>, Error> {
+    async fn send_transaction<'a>(
+        &'a self,
+        tx: CreateTransactionRequest,
+    ) -> Result<PendingTransaction<'a, Self::Provider>, Error> {
         let response = self.send_transaction_without_confirm::<CreateTransactionResponse>(tx).await?;
 
-        Ok(Transaction::new(response.tran_id, self.provider()))
+        Ok(PendingTransaction::new(response.tran_id, self.provider()))
     }
 
     /// If there is any signer middleware, will sign it first and then send it.
@@ -49,12 +54,12 @@ pub trait Middleware: Sync + Send + std::fmt::Debug {
         self.inner().send_transaction_without_confirm(tx).await
     }
 
-    fn sign(&self, data: &[u8]) -> Result<Signature, Error> {
-        self.inner().sign(data)
+    async fn sign(&self, data: &[u8]) -> Result<Signature, Error> {
+        self.inner().sign(data).await
     }
 
-    fn sign_transaction(&self, tx: &CreateTransactionRequest) -> Result<Signature, Error> {
-        self.inner().sign_transaction(tx)
+    async fn sign_transaction(&self, tx: &CreateTransactionRequest) -> Result<Signature, Error> {
+        self.inner().sign_transaction(tx).await
     }
 
     /// Directly calls CreateTransaction JSON-RPC endpoint.
@@ -80,6 +85,13 @@ pub trait Middleware: Sync + Send + std::fmt::Debug {
         self.inner().get_transaction(tx_hash).await
     }
 
+    /// Fetches the given transactions in a single batch request.
+    ///
+    /// Results are in the order of the hashes, so a missing transaction doesn't fail the others.
+    async fn get_transactions(&self, tx_hashes: &[TxHash]) -> Result<Vec<Result<GetTransactionResponse, Error>>, Error> {
+        self.inner().get_transactions(tx_hashes).await
+    }
+
     async fn get_soft_confirmed_transaction(&self, tx_hash: &TxHash) -> Result<GetTransactionResponse, Error> {
         self.inner().get_soft_confirmed_transaction(tx_hash).await
     }
@@ -92,32 +104,39 @@ pub trait Middleware: Sync + Send + std::fmt::Debug {
         self.inner().get_current_ds_comm().await
     }
 
-    async fn get_ds_block(&self, lock_num: &str) -> Result<DsBlock, Error> {
-        self.inner().get_ds_block(lock_num).await
+    /// Fetches a DS block. [BlockId::Latest] is fetched using `GetLatestDsBlock`.
+    async fn get_ds_block(&self, block: BlockId) -> Result<DsBlock, Error> {
+        self.inner().get_ds_block(block).await
     }
 
-    async fn get_ds_block_verbose(&self, lock_num: &str) -> Result<DsBlockVerbose, Error> {
-        self.inner().get_ds_block_verbose(lock_num).await
+    async fn get_ds_block_verbose(&self, block: BlockId) -> Result<DsBlockVerbose, Error> {
+        self.inner().get_ds_block_verbose(block).await
     }
 
     async fn ds_block_listing(&self, max: u32) -> Result<BlockList, Error> {
         self.inner().ds_block_listing(max).await
     }
 
-    async fn get_tx_block(&self, block_num: &str) -> Result<TxBlock, Error> {
-        self.inner().get_tx_block(block_num).await
+    /// Fetches a tx block. [BlockId::Latest] is fetched using `GetLatestTxBlock`.
+    async fn get_tx_block(&self, block: BlockId) -> Result<TxBlock, Error> {
+        self.inner().get_tx_block(block).await
     }
 
-    async fn get_tx_block_verbose(&self, block_num: &str) -> Result<TxBlockVerbose, Error> {
-        self.inner().get_tx_block_verbose(block_num).await
+    /// Fetches the tx blocks in the given range in a single batch request.
+    async fn get_tx_blocks(&self, block_nums: Range<u64>) -> Result<Vec<Result<TxBlock, Error>>, Error> {
+        self.inner().get_tx_blocks(block_nums).await
+    }
+
+    async fn get_tx_block_verbose(&self, block: BlockId) -> Result<TxBlockVerbose, Error> {
+        self.inner().get_tx_block_verbose(block).await
     }
 
     async fn tx_block_listing(&self, max: u32) -> Result<BlockList, Error> {
         self.inner().tx_block_listing(max).await
     }
 
-    async fn get_miner_info(&self, ds_block_number: &str) -> Result<MinerInfo, Error> {
-        self.inner().get_miner_info(ds_block_number).await
+    async fn get_miner_info(&self, ds_block: BlockId) -> Result<MinerInfo, Error> {
+        self.inner().get_miner_info(ds_block).await
     }
 
     async fn get_blockchain_info(&self) -> Result<BlockchainInfo, Error> {
@@ -136,7 +155,7 @@ pub trait Middleware: Sync + Send + std::fmt::Debug {
         self.inner().get_latest_ds_block().await
     }
 
-    async fn get_num_ds_blocks(&self) -> Result<String, Error> {
+    async fn get_num_ds_blocks(&self) -> Result<u64, Error> {
         self.inner().get_num_ds_blocks().await
     }
 
@@ -148,7 +167,7 @@ pub trait Middleware: Sync + Send + std::fmt::Debug {
         self.inner().get_latest_tx_block().await
     }
 
-    async fn get_num_tx_blocks(&self) -> Result<String, Error> {
+    async fn get_num_tx_blocks(&self) -> Result<u64, Error> {
         self.inner().get_num_tx_blocks().await
     }
 
@@ -156,7 +175,7 @@ pub trait Middleware: Sync + Send + std::fmt::Debug {
         self.inner().get_tx_block_rate().await
     }
 
-    async fn get_num_transactions(&self) -> Result<String, Error> {
+    async fn get_num_transactions(&self) -> Result<u64, Error> {
         self.inner().get_num_transactions().await
     }
 
@@ -164,11 +183,11 @@ pub trait Middleware: Sync + Send + std::fmt::Debug {
         self.inner().get_transaction_rate().await
     }
 
-    async fn get_current_mini_epoch(&self) -> Result<String, Error> {
+    async fn get_current_mini_epoch(&self) -> Result<u64, Error> {
         self.inner().get_current_mini_epoch().await
     }
 
-    async fn get_current_ds_epoch(&self) -> Result<String, Error> {
+    async fn get_current_ds_epoch(&self) -> Result<u64, Error> {
         self.inner().get_current_ds_epoch().await
     }
 
@@ -196,31 +215,86 @@ pub trait Middleware: Sync + Send + std::fmt::Debug {
         self.inner().get_recent_transactions().await
     }
 
-    async fn get_transactions_for_tx_block(&self, tx_block: &str) -> Result<Vec<Vec<String>>, Error> {
+    async fn get_transactions_for_tx_block(&self, tx_block: BlockId) -> Result<Vec<Vec<TxHash>>, Error> {
         self.inner().get_transactions_for_tx_block(tx_block).await
     }
 
-    async fn get_txn_bodies_for_tx_block_ex(&self, tx_block: &str, page_num: &str) -> Result<TxnBodiesForTxBlockEx, Error> {
+    async fn get_txn_bodies_for_tx_block_ex(&self, tx_block: BlockId, page_num: u32) -> Result<TxnBodiesForTxBlockEx, Error> {
         self.inner().get_txn_bodies_for_tx_block_ex(tx_block, page_num).await
     }
 
-    async fn get_txn_bodies_for_tx_block(&self, tx_block: &str) -> Result<Vec<GetTransactionResponse>, Error> {
+    async fn get_txn_bodies_for_tx_block(&self, tx_block: BlockId) -> Result<Vec<GetTransactionResponse>, Error> {
         self.inner().get_txn_bodies_for_tx_block(tx_block).await
     }
 
-    async fn get_transactions_for_tx_block_ex(&self, tx_block: &str, page_num: &str) -> Result<TransactionsForTxBlockEx, Error> {
+    /// Streams the bodies of all transactions of a tx block, walking through all of its pages. Blocks without
+    /// transactions give an empty stream.
+    ///
+    /// Pages are fetched concurrently, at most [Provider::max_concurrent_requests] at once, and their
+    /// transactions are yielded in order. Requests go through the middleware stack, e.g. to be retried.
+    ///
+    /// # Example
+    /// ```no_run
+    /// use futures::TryStreamExt;
+    /// use zilliqa_rs::core::BlockId;
+    /// use zilliqa_rs::middlewares::Middleware;
+    /// use zilliqa_rs::providers::{Http, Provider};
+    ///
+    /// #[tokio::main]
+    /// async fn main() -> anyhow::Result<()> {
+    ///     let provider = Provider::<Http>::try_from("https://api.zilliqa.com")?;
+    ///     let transactions: Vec<_> = provider.stream_txn_bodies(BlockId::Number(3357911)).try_collect().await?;
+    ///     println!("{}", transactions.len());
+    ///     Ok(())
+    /// }
+    /// ```
+    fn stream_txn_bodies(&self, block: BlockId) -> BoxStream<'_, Result<GetTransactionResponse, Error>> {
+        stream::txn_bodies(self, block, self.provider().max_concurrent_requests())
+    }
+
+    /// Streams the bodies of all transactions of the tx blocks in the given range, e.g. to export an epoch.
+    ///
+    /// Blocks are fetched concurrently, at most [Provider::max_concurrent_requests] at once, and their
+    /// transactions are yielded in order. Blocks without transactions are skipped.
+    ///
+    /// # Example
+    /// ```no_run
+    /// use futures::StreamExt;
+    /// use zilliqa_rs::middlewares::{retry::RetryMiddleware, Middleware};
+    /// use zilliqa_rs::providers::{Http, Provider};
+    ///
+    /// #[tokio::main]
+    /// async fn main() -> anyhow::Result<()> {
+    ///     let provider = Provider::<Http>::try_from("https://api.zilliqa.com")?.with_max_concurrent_requests(8);
+    ///     let middleware = RetryMiddleware::new(provider);
+    ///     let mut transactions = middleware.stream_block_range(3357900..3357911);
+    ///     while let Some(tx) = transactions.next().await {
+    ///         println!("{}", tx?.id);
+    ///     }
+    ///     Ok(())
+    /// }
+    /// ```
+    fn stream_block_range(&self, blocks: Range<u64>) -> BoxStream<'_, Result<GetTransactionResponse, Error>> {
+        stream::block_range(self, blocks, self.provider().max_concurrent_requests())
+    }
+
+    async fn get_transactions_for_tx_block_ex(
+        &self,
+        tx_block: BlockId,
+        page_num: u32,
+    ) -> Result<TransactionsForTxBlockEx, Error> {
         self.inner().get_transactions_for_tx_block_ex(tx_block, page_num).await
     }
 
-    async fn get_num_txns_tx_epoch(&self, epoch: &str) -> Result<String, Error> {
+    async fn get_num_txns_tx_epoch(&self, epoch: BlockId) -> Result<u64, Error> {
         self.inner().get_num_txns_tx_epoch(epoch).await
     }
 
-    async fn get_num_txns_ds_epoch(&self, epoch: &str) -> Result<String, Error> {
+    async fn get_num_txns_ds_epoch(&self, epoch: BlockId) -> Result<u64, Error> {
         self.inner().get_num_txns_ds_epoch(epoch).await
     }
 
-    async fn get_minimum_gas_price(&self) -> Result<String, Error> {
+    async fn get_minimum_gas_price(&self) -> Result<u128, Error> {
         self.inner().get_minimum_gas_price().await
     }
 
@@ -259,7 +333,7 @@ pub trait Middleware: Sync + Send + std::fmt::Debug {
         &self,
         contract_address: &ZilAddress,
         hash: &str,
-        tx_block: &str,
+        tx_block: BlockId,
     ) -> Result<serde_json::Value, Error> {
         self.inner().get_state_proof(contract_address, hash, tx_block).await
     }
@@ -1,3 +1,6 @@
+pub mod gas_oracle;
 pub mod middleware;
+pub mod nonce_manager;
+pub mod retry;
 pub mod signer;
 pub use middleware::Middleware;
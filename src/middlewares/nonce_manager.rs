@@ -0,0 +1,259 @@
+use std::sync::atomic::{AtomicBool, AtomicU64, Ordering};
+
+use async_trait::async_trait;
+use jsonrpsee::core::ClientError;
+use serde::de::DeserializeOwned;
+use tokio::sync::Mutex;
+
+use crate::{
+    core::{CreateTransactionRequest, ZilAddress},
+    providers::RPCErrorCode,
+    Error,
+};
+
+use super::Middleware;
+
+/// A middleware that keeps track of the nonce of an account locally.
+///
+/// The nonce is fetched once using `GetBalance` and then every transaction without a nonce gets the
+/// next one, so many transactions can be sent concurrently from a single account. It should be placed
+/// above the signer middleware, so the signer doesn't query the nonce itself.
+///
+/// If the node rejects a transaction because of its nonce, e.g. because another client sent a
+/// transaction from the same account, the nonce is fetched again and the transaction is sent once more.
+///
+/// # Example
+/// ```
+/// use zilliqa_rs::middlewares::{nonce_manager::NonceManagerMiddleware, signer::SignerMiddleware};
+/// use zilliqa_rs::providers::{Http, Provider};
+/// use zilliqa_rs::signers::LocalWallet;
+///
+/// #[tokio::main]
+/// async fn main() -> anyhow::Result<()> {
+///     let wallet = LocalWallet::create_random()?;
+///     let address = wallet.address.clone();
+///     let provider = Provider::<Http>::try_from("http://127.0.0.1:5555")?.with_chain_id(222);
+///     let provider = NonceManagerMiddleware::new(SignerMiddleware::new(provider, wallet), address);
+///     Ok(())
+/// }
+/// ```
+#[derive(Debug)]
+pub struct NonceManagerMiddleware<M> {
+    inner: M,
+    address: ZilAddress,
+    initialized: AtomicBool,
+    nonce: AtomicU64,
+    init_guard: Mutex<()>,
+}
+
+impl<M: Middleware> NonceManagerMiddleware<M> {
+    /// Creates a new NonceManagerMiddleware which manages the nonce of the given address.
+    pub fn new(inner: M, address: ZilAddress) -> Self {
+        Self {
+            inner,
+            address,
+            initialized: AtomicBool::new(false),
+            nonce: AtomicU64::new(0),
+            init_guard: Mutex::new(()),
+        }
+    }
+
+    /// The address whose nonce is managed.
+    pub fn address(&self) -> &ZilAddress {
+        &self.address
+    }
+
+    /// Forgets the local nonce. It's fetched from the network again before the next transaction.
+    pub fn reset(&self) {
+        self.initialized.store(false, Ordering::SeqCst);
+    }
+
+    /// Fetches the nonce from the network if it isn't fetched yet, and returns the next nonce to use.
+    pub async fn initialize_nonce(&self) -> Result<u64, Error> {
+        if self.initialized.load(Ordering::SeqCst) {
+            return Ok(self.nonce.load(Ordering::SeqCst));
+        }
+
+        let _guard = self.init_guard.lock().await;
+
+        // Another task may have fetched the nonce while we were waiting for the lock.
+        if self.initialized.load(Ordering::SeqCst) {
+            return Ok(self.nonce.load(Ordering::SeqCst));
+        }
+
+        let balance = self.inner.get_balance(&self.address).await?;
+        self.nonce.store(balance.nonce + 1, Ordering::SeqCst);
+        self.initialized.store(true, Ordering::SeqCst);
+
+        Ok(balance.nonce + 1)
+    }
+
+    /// Returns the next nonce and increments the local one.
+    pub async fn next_nonce(&self) -> Result<u64, Error> {
+        self.initialize_nonce().await?;
+        Ok(self.nonce.fetch_add(1, Ordering::SeqCst))
+    }
+}
+
+/// Checks if the given error is a rejection of a transaction because of its nonce.
+fn is_nonce_error(error: &Error) -> bool {
+    match error {
+        Error::JsonRpcError(ClientError::Call(e)) => {
+            RPCErrorCode::from_error(error) == Some(RPCErrorCode::RpcVerifyRejected)
+                && e.message().to_lowercase().contains("nonce")
+        }
+        _ => false,
+    }
+}
+
+#[async_trait]
+impl<M: Middleware> Middleware for NonceManagerMiddleware<M> {
+    type Provider = M::Provider;
+
+    type Inner = M;
+
+    fn inner(&self) -> &Self::Inner {
+        &self.inner
+    }
+
+    async fn send_transaction_without_confirm<T: Send + DeserializeOwned>(
+        &self,
+        mut tx: CreateTransactionRequest,
+    ) -> Result<T, Error> {
+        // Transactions of other accounts of a multi-account signer are left untouched.
+        let other_account = tx.from.as_ref().is_some_and(|from| from != &self.address);
+        if tx.nonce != u64::default() || other_account {
+            return self.inner.send_transaction_without_confirm(tx).await;
+        }
+
+        tx.nonce = self.next_nonce().await?;
+        match self.inner.send_transaction_without_confirm(tx.clone()).await {
+            Err(e) if is_nonce_error(&e) => {
+                self.reset();
+                tx.nonce = self.next_nonce().await?;
+                self.inner.send_transaction_without_confirm(tx).await
+            }
+            result => result,
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use jsonrpsee::{core::ClientError, types::ErrorObject};
+    use serde_json::{json, Value};
+
+    use crate::{
+        core::CreateTransactionRequest,
+        middlewares::{signer::SignerMiddleware, Middleware},
+        providers::{mock::MockClient, Provider, RPCErrorCode},
+        signers::LocalWallet,
+    };
+
+    use super::NonceManagerMiddleware;
+
+    type TestMiddleware = NonceManagerMiddleware<SignerMiddleware<Provider<MockClient>, LocalWallet>>;
+
+    fn nonce_manager(mock: &MockClient) -> TestMiddleware {
+        let wallet = LocalWallet::create_random().unwrap();
+        let address = wallet.address.clone();
+        NonceManagerMiddleware::new(SignerMiddleware::new(Provider::new(mock.clone(), 222), wallet), address)
+    }
+
+    fn transfer() -> CreateTransactionRequest {
+        CreateTransactionRequest {
+            to_addr: "0x381f4008505e940ad7681ec3468a719060caf796".parse().unwrap(),
+            amount: 1,
+            gas_price: 2000000000,
+            gas_limit: 50,
+            ..Default::default()
+        }
+    }
+
+    fn tx_response() -> Value {
+        json!({"TranID": "bdadfd994f452df803cc223d1f417b02830ac96dbe5edad1b9f8d58613f95206", "Info": ""})
+    }
+
+    fn sent_nonces(mock: &MockClient) -> Vec<u64> {
+        mock.requests()
+            .into_iter()
+            .filter(|(method, _)| method == "CreateTransaction")
+            .map(|(_, params)| params[0]["nonce"].as_u64().unwrap())
+            .collect()
+    }
+
+    fn balance_requests(mock: &MockClient) -> usize {
+        mock.requests().iter().filter(|(method, _)| method == "GetBalance").count()
+    }
+
+    #[tokio::test]
+    async fn concurrent_transactions_should_get_distinct_nonces() {
+        let mock = MockClient::default();
+        mock.push(json!({"balance": "1000", "nonce": 5}));
+        for _ in 0..3 {
+            mock.push(tx_response());
+        }
+
+        let provider = nonce_manager(&mock);
+        let (a, b, c) = tokio::join!(
+            provider.send_transaction_without_confirm::<Value>(transfer()),
+            provider.send_transaction_without_confirm::<Value>(transfer()),
+            provider.send_transaction_without_confirm::<Value>(transfer()),
+        );
+        a.unwrap();
+        b.unwrap();
+        c.unwrap();
+
+        let mut nonces = sent_nonces(&mock);
+        nonces.sort();
+        assert_eq!(nonces, vec![6, 7, 8]);
+        assert_eq!(balance_requests(&mock), 1);
+    }
+
+    #[tokio::test]
+    async fn nonce_should_be_resynced_after_nonce_error() {
+        let mock = MockClient::default();
+        mock.push(json!({"balance": "1000", "nonce": 5}));
+        mock.push_error(ClientError::Call(ErrorObject::owned(
+            RPCErrorCode::RpcVerifyRejected as i32,
+            "Nonce (6) lower than current (9)",
+            None::<()>,
+        )));
+        mock.push(json!({"balance": "1000", "nonce": 9}));
+        mock.push(tx_response());
+
+        let provider = nonce_manager(&mock);
+        provider.send_transaction_without_confirm::<Value>(transfer()).await.unwrap();
+
+        assert_eq!(sent_nonces(&mock), vec![6, 10]);
+        assert_eq!(provider.next_nonce().await.unwrap(), 11);
+    }
+
+    #[tokio::test]
+    async fn reset_should_fetch_nonce_again() {
+        let mock = MockClient::default();
+        mock.push(json!({"balance": "1000", "nonce": 5}));
+        mock.push(json!({"balance": "1000", "nonce": 7}));
+
+        let provider = nonce_manager(&mock);
+        assert_eq!(provider.next_nonce().await.unwrap(), 6);
+        assert_eq!(provider.next_nonce().await.unwrap(), 7);
+
+        provider.reset();
+        assert_eq!(provider.next_nonce().await.unwrap(), 8);
+        assert_eq!(balance_requests(&mock), 2);
+    }
+
+    #[tokio::test]
+    async fn explicit_nonce_should_be_left_untouched() {
+        let mock = MockClient::default();
+        mock.push(tx_response());
+
+        let provider = nonce_manager(&mock);
+        let tx = CreateTransactionRequest { nonce: 42, ..transfer() };
+        provider.send_transaction_without_confirm::<Value>(tx).await.unwrap();
+
+        assert_eq!(sent_nonces(&mock), vec![42]);
+        assert_eq!(balance_requests(&mock), 0);
+    }
+}
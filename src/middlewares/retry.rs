@@ -0,0 +1,490 @@
+use std::{future::Future, ops::Range, time::Duration};
+
+use async_trait::async_trait;
+use jsonrpsee::{core::ClientError, http_client::transport::Error as HttpTransportError};
+use serde::de::DeserializeOwned;
+
+use crate::{
+    contract::ScillaVariable,
+    core::{types::*, BlockId, TxHash, ZilAddress},
+    providers::RPCErrorCode,
+    Error,
+};
+
+use super::Middleware;
+
+/// Decides which failed requests are retried and how long to wait between attempts.
+#[derive(Debug, Clone)]
+pub struct RetryPolicy {
+    /// Maximum number of retries after the first attempt.
+    pub max_retries: u32,
+    /// Delay before the first retry. It's doubled for every subsequent retry.
+    pub initial_backoff: Duration,
+    /// Upper bound of the delay between two attempts.
+    pub max_backoff: Duration,
+    /// If set, every delay is randomized between half and all of its value.
+    pub jitter: bool,
+}
+
+impl Default for RetryPolicy {
+    fn default() -> Self {
+        Self {
+            max_retries: 3,
+            initial_backoff: Duration::from_millis(500),
+            max_backoff: Duration::from_secs(10),
+            jitter: true,
+        }
+    }
+}
+
+impl RetryPolicy {
+    /// Returns the delay before the given retry, starting from zero.
+    pub fn backoff(&self, retry: u32) -> Duration {
+        let backoff = self
+            .initial_backoff
+            .saturating_mul(2_u32.saturating_pow(retry))
+            .min(self.max_backoff);
+
+        if self.jitter {
+            let half = backoff / 2;
+            half + half.mul_f64(rand::random::<f64>())
+        } else {
+            backoff
+        }
+    }
+
+    /// Checks if the given error is transient, i.e. sending the same request again may succeed.
+    ///
+    /// Network failures, timeouts, throttling and errors of the node itself are transient. Errors which
+    /// are caused by the request, like `RpcInvalidParams` or `RpcVerifyRejected`, are not.
+    pub fn is_retryable(&self, error: &Error) -> bool {
+        match error {
+            Error::JsonRpcError(ClientError::Call(_)) => matches!(
+                RPCErrorCode::from_error(error),
+                Some(RPCErrorCode::RpcInternalError | RPCErrorCode::RpcInWarmup)
+            ),
+            Error::JsonRpcError(ClientError::Transport(e)) => match e.downcast_ref::<HttpTransportError>() {
+                Some(HttpTransportError::Rejected { status_code }) => matches!(status_code, 408 | 429 | 500..=599),
+                Some(HttpTransportError::Http(_)) | None => true,
+                Some(_) => false,
+            },
+            Error::JsonRpcError(ClientError::RequestTimeout | ClientError::RestartNeeded(_)) => true,
+            Error::WsError(_) | Error::WsHandshakeError(_) => true,
+            _ => false,
+        }
+    }
+}
+
+/// A middleware that retries failed requests with exponential backoff.
+///
+/// Read-only requests are retried whenever they fail with a transient error. Transactions are retried
+/// only if sending them again is idempotent, that is, their nonce is already fixed. If the first
+/// attempt reached the network but its response got lost, the retry may be rejected by the node.
+///
+/// # Example
+/// ```
+/// use std::time::Duration;
+/// use zilliqa_rs::middlewares::retry::{RetryMiddleware, RetryPolicy};
+/// use zilliqa_rs::providers::{Http, Provider};
+///
+/// #[tokio::main]
+/// async fn main() -> anyhow::Result<()> {
+///     let provider = Provider::<Http>::try_from("http://127.0.0.1:5555")?.with_chain_id(222);
+///     let provider = RetryMiddleware::new(provider).with_policy(RetryPolicy {
+///         max_retries: 5,
+///         initial_backoff: Duration::from_millis(200),
+///         ..Default::default()
+///     });
+///     Ok(())
+/// }
+/// ```
+#[derive(Debug)]
+pub struct RetryMiddleware<M> {
+    inner: M,
+    policy: RetryPolicy,
+}
+
+impl<M: Middleware> RetryMiddleware<M> {
+    /// Creates a new RetryMiddleware with the default policy.
+    pub fn new(inner: M) -> Self {
+        Self {
+            inner,
+            policy: RetryPolicy::default(),
+        }
+    }
+
+    /// Replaces the retry policy.
+    pub fn with_policy(mut self, policy: RetryPolicy) -> Self {
+        self.policy = policy;
+        self
+    }
+
+    async fn retry<T, F, Fut>(&self, request: F) -> Result<T, Error>
+    where
+        F: Fn() -> Fut + Send + Sync,
+        Fut: Future<Output = Result<T, Error>> + Send,
+        T: Send,
+    {
+        let mut retry = 0;
+        loop {
+            match request().await {
+                Err(e) if retry < self.policy.max_retries && self.policy.is_retryable(&e) => {
+                    tokio::time::sleep(self.policy.backoff(retry)).await;
+                    retry += 1;
+                }
+                result => return result,
+            }
+        }
+    }
+}
+
+#[async_trait]
+impl<M: Middleware> Middleware for RetryMiddleware<M> {
+    type Provider = M::Provider;
+
+    type Inner = M;
+
+    fn inner(&self) -> &Self::Inner {
+        &self.inner
+    }
+
+    async fn send_transaction_without_confirm<T: Send + DeserializeOwned>(
+        &self,
+        tx: CreateTransactionRequest,
+    ) -> Result<T, Error> {
+        if tx.nonce == u64::default() {
+            return self.inner.send_transaction_without_confirm(tx).await;
+        }
+
+        self.retry(|| self.inner.send_transaction_without_confirm(tx.clone())).await
+    }
+
+    async fn create_transaction<T: Send + DeserializeOwned>(&self, tx: CreateTransactionRequest) -> Result<T, Error> {
+        if tx.signature.is_none() {
+            return self.inner.create_transaction(tx).await;
+        }
+
+        self.retry(|| self.inner.create_transaction(tx.clone())).await
+    }
+
+    async fn get_transaction_status(&self, tx_hash: &TxHash) -> Result<TransactionStatus, Error> {
+        self.retry(|| self.inner.get_transaction_status(tx_hash)).await
+    }
+
+    async fn get_transaction(&self, tx_hash: &TxHash) -> Result<GetTransactionResponse, Error> {
+        self.retry(|| self.inner.get_transaction(tx_hash)).await
+    }
+
+    async fn get_transactions(&self, tx_hashes: &[TxHash]) -> Result<Vec<Result<GetTransactionResponse, Error>>, Error> {
+        self.retry(|| self.inner.get_transactions(tx_hashes)).await
+    }
+
+    async fn get_soft_confirmed_transaction(&self, tx_hash: &TxHash) -> Result<GetTransactionResponse, Error> {
+        self.retry(|| self.inner.get_soft_confirmed_transaction(tx_hash)).await
+    }
+
+    async fn get_balance(&self, address: &str) -> Result<BalanceResponse, Error> {
+        self.retry(|| self.inner.get_balance(address)).await
+    }
+
+    async fn get_current_ds_comm(&self) -> Result<GetCurrentDsCommResponse, Error> {
+        self.retry(|| self.inner.get_current_ds_comm()).await
+    }
+
+    async fn get_ds_block(&self, block: BlockId) -> Result<DsBlock, Error> {
+        self.retry(|| self.inner.get_ds_block(block)).await
+    }
+
+    async fn get_ds_block_verbose(&self, block: BlockId) -> Result<DsBlockVerbose, Error> {
+        self.retry(|| self.inner.get_ds_block_verbose(block)).await
+    }
+
+    async fn ds_block_listing(&self, max: u32) -> Result<BlockList, Error> {
+        self.retry(|| self.inner.ds_block_listing(max)).await
+    }
+
+    async fn get_tx_block(&self, block: BlockId) -> Result<TxBlock, Error> {
+        self.retry(|| self.inner.get_tx_block(block)).await
+    }
+
+    async fn get_tx_blocks(&self, block_nums: Range<u64>) -> Result<Vec<Result<TxBlock, Error>>, Error> {
+        self.retry(|| self.inner.get_tx_blocks(block_nums.clone())).await
+    }
+
+    async fn get_tx_block_verbose(&self, block: BlockId) -> Result<TxBlockVerbose, Error> {
+        self.retry(|| self.inner.get_tx_block_verbose(block)).await
+    }
+
+    async fn tx_block_listing(&self, max: u32) -> Result<BlockList, Error> {
+        self.retry(|| self.inner.tx_block_listing(max)).await
+    }
+
+    async fn get_miner_info(&self, ds_block: BlockId) -> Result<MinerInfo, Error> {
+        self.retry(|| self.inner.get_miner_info(ds_block)).await
+    }
+
+    async fn get_blockchain_info(&self) -> Result<BlockchainInfo, Error> {
+        self.retry(|| self.inner.get_blockchain_info()).await
+    }
+
+    async fn get_node_type(&self) -> Result<String, Error> {
+        self.retry(|| self.inner.get_node_type()).await
+    }
+
+    async fn get_sharding_structure(&self) -> Result<ShardingStructure, Error> {
+        self.retry(|| self.inner.get_sharding_structure()).await
+    }
+
+    async fn get_latest_ds_block(&self) -> Result<DsBlock, Error> {
+        self.retry(|| self.inner.get_latest_ds_block()).await
+    }
+
+    async fn get_num_ds_blocks(&self) -> Result<u64, Error> {
+        self.retry(|| self.inner.get_num_ds_blocks()).await
+    }
+
+    async fn get_ds_block_rate(&self) -> Result<f32, Error> {
+        self.retry(|| self.inner.get_ds_block_rate()).await
+    }
+
+    async fn get_latest_tx_block(&self) -> Result<TxBlock, Error> {
+        self.retry(|| self.inner.get_latest_tx_block()).await
+    }
+
+    async fn get_num_tx_blocks(&self) -> Result<u64, Error> {
+        self.retry(|| self.inner.get_num_tx_blocks()).await
+    }
+
+    async fn get_tx_block_rate(&self) -> Result<f32, Error> {
+        self.retry(|| self.inner.get_tx_block_rate()).await
+    }
+
+    async fn get_num_transactions(&self) -> Result<u64, Error> {
+        self.retry(|| self.inner.get_num_transactions()).await
+    }
+
+    async fn get_transaction_rate(&self) -> Result<f32, Error> {
+        self.retry(|| self.inner.get_transaction_rate()).await
+    }
+
+    async fn get_current_mini_epoch(&self) -> Result<u64, Error> {
+        self.retry(|| self.inner.get_current_mini_epoch()).await
+    }
+
+    async fn get_current_ds_epoch(&self) -> Result<u64, Error> {
+        self.retry(|| self.inner.get_current_ds_epoch()).await
+    }
+
+    async fn get_prev_difficulty(&self) -> Result<u32, Error> {
+        self.retry(|| self.inner.get_prev_difficulty()).await
+    }
+
+    async fn get_num_peers(&self) -> Result<u32, Error> {
+        self.retry(|| self.inner.get_num_peers()).await
+    }
+
+    async fn get_prev_ds_difficulty(&self) -> Result<u32, Error> {
+        self.retry(|| self.inner.get_prev_ds_difficulty()).await
+    }
+
+    async fn get_total_coin_supply(&self) -> Result<String, Error> {
+        self.retry(|| self.inner.get_total_coin_supply()).await
+    }
+
+    async fn get_total_coin_supply_as_int(&self) -> Result<u128, Error> {
+        self.retry(|| self.inner.get_total_coin_supply_as_int()).await
+    }
+
+    async fn get_recent_transactions(&self) -> Result<TxList, Error> {
+        self.retry(|| self.inner.get_recent_transactions()).await
+    }
+
+    async fn get_transactions_for_tx_block(&self, tx_block: BlockId) -> Result<Vec<Vec<TxHash>>, Error> {
+        self.retry(|| self.inner.get_transactions_for_tx_block(tx_block)).await
+    }
+
+    async fn get_txn_bodies_for_tx_block_ex(&self, tx_block: BlockId, page_num: u32) -> Result<TxnBodiesForTxBlockEx, Error> {
+        self.retry(|| self.inner.get_txn_bodies_for_tx_block_ex(tx_block, page_num))
+            .await
+    }
+
+    async fn get_txn_bodies_for_tx_block(&self, tx_block: BlockId) -> Result<Vec<GetTransactionResponse>, Error> {
+        self.retry(|| self.inner.get_txn_bodies_for_tx_block(tx_block)).await
+    }
+
+    async fn get_transactions_for_tx_block_ex(
+        &self,
+        tx_block: BlockId,
+        page_num: u32,
+    ) -> Result<TransactionsForTxBlockEx, Error> {
+        self.retry(|| self.inner.get_transactions_for_tx_block_ex(tx_block, page_num))
+            .await
+    }
+
+    async fn get_num_txns_tx_epoch(&self, epoch: BlockId) -> Result<u64, Error> {
+        self.retry(|| self.inner.get_num_txns_tx_epoch(epoch)).await
+    }
+
+    async fn get_num_txns_ds_epoch(&self, epoch: BlockId) -> Result<u64, Error> {
+        self.retry(|| self.inner.get_num_txns_ds_epoch(epoch)).await
+    }
+
+    async fn get_minimum_gas_price(&self) -> Result<u128, Error> {
+        self.retry(|| self.inner.get_minimum_gas_price()).await
+    }
+
+    async fn get_smart_contracts(&self, owner: &ZilAddress) -> Result<SmartContracts, Error> {
+        self.retry(|| self.inner.get_smart_contracts(owner)).await
+    }
+
+    async fn get_contract_address_from_transaction_id(&self, tx_hash: &TxHash) -> Result<String, Error> {
+        self.retry(|| self.inner.get_contract_address_from_transaction_id(tx_hash))
+            .await
+    }
+
+    async fn get_smart_contract_code(&self, contract_address: &ZilAddress) -> Result<SmartContractCode, Error> {
+        self.retry(|| self.inner.get_smart_contract_code(contract_address)).await
+    }
+
+    async fn get_smart_contract_init(&self, contract_address: &ZilAddress) -> Result<Vec<ScillaVariable>, Error> {
+        self.retry(|| self.inner.get_smart_contract_init(contract_address)).await
+    }
+
+    async fn get_smart_contract_state<T: Send + DeserializeOwned>(&self, contract_address: &ZilAddress) -> Result<T, Error> {
+        self.retry(|| self.inner.get_smart_contract_state(contract_address)).await
+    }
+
+    async fn get_smart_contract_sub_state(
+        &self,
+        contract_address: &ZilAddress,
+        variable_name: &str,
+        indices: &[&str],
+    ) -> Result<serde_json::Value, Error> {
+        self.retry(|| {
+            self.inner
+                .get_smart_contract_sub_state(contract_address, variable_name, indices)
+        })
+        .await
+    }
+
+    async fn get_state_proof(
+        &self,
+        contract_address: &ZilAddress,
+        hash: &str,
+        tx_block: BlockId,
+    ) -> Result<serde_json::Value, Error> {
+        self.retry(|| self.inner.get_state_proof(contract_address, hash, tx_block))
+            .await
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::time::Duration;
+
+    use claim::{assert_err, assert_ok};
+    use jsonrpsee::{core::ClientError, types::ErrorObject};
+    use serde_json::json;
+
+    use crate::{
+        core::CreateTransactionRequest,
+        middlewares::Middleware,
+        providers::{mock::MockClient, Provider, RPCErrorCode},
+        transaction::Version,
+    };
+
+    use super::{RetryMiddleware, RetryPolicy};
+
+    fn call_error(code: RPCErrorCode) -> ClientError {
+        ClientError::Call(ErrorObject::owned(code as i32, "error", None::<()>))
+    }
+
+    fn retry_middleware(mock: &MockClient) -> RetryMiddleware<Provider<MockClient>> {
+        RetryMiddleware::new(Provider::new(mock.clone(), 222)).with_policy(RetryPolicy {
+            max_retries: 2,
+            initial_backoff: Duration::from_millis(1),
+            max_backoff: Duration::from_millis(2),
+            jitter: false,
+        })
+    }
+
+    #[tokio::test]
+    async fn transient_errors_should_be_retried() {
+        let mock = MockClient::default();
+        mock.push_error(ClientError::RequestTimeout);
+        mock.push_error(call_error(RPCErrorCode::RpcInWarmup));
+        mock.push("10");
+
+        let provider = retry_middleware(&mock);
+        assert_eq!(provider.get_num_tx_blocks().await.unwrap(), 10);
+        assert_eq!(mock.requests().len(), 3);
+    }
+
+    #[tokio::test]
+    async fn retries_should_stop_after_max_retries() {
+        let mock = MockClient::default();
+        for _ in 0..3 {
+            mock.push_error(ClientError::RequestTimeout);
+        }
+
+        let provider = retry_middleware(&mock);
+        assert_err!(provider.get_num_tx_blocks().await);
+        assert_eq!(mock.requests().len(), 3);
+    }
+
+    #[tokio::test]
+    async fn rejected_and_invalid_requests_should_not_be_retried() {
+        for code in [RPCErrorCode::RpcVerifyRejected, RPCErrorCode::RpcInvalidParams] {
+            let mock = MockClient::default();
+            mock.push_error(call_error(code));
+
+            let provider = retry_middleware(&mock);
+            assert_err!(provider.get_balance("0x381f4008505e940ad7681ec3468a719060caf796").await);
+            assert_eq!(mock.requests().len(), 1);
+        }
+    }
+
+    #[tokio::test]
+    async fn only_signed_transactions_should_be_retried() {
+        let mock = MockClient::default();
+        mock.push_error(ClientError::RequestTimeout);
+
+        let provider = retry_middleware(&mock);
+        let tx = CreateTransactionRequest {
+            version: Version::new(222),
+            nonce: 1,
+            ..Default::default()
+        };
+        assert_err!(provider.create_transaction::<serde_json::Value>(tx.clone()).await);
+        assert_eq!(mock.requests().len(), 1);
+
+        mock.push_error(ClientError::RequestTimeout);
+        mock.push(json!({"TranID": "bdadfd994f452df803cc223d1f417b02830ac96dbe5edad1b9f8d58613f95206", "Info": ""}));
+        let tx = CreateTransactionRequest {
+            signature: Some("00".to_string()),
+            ..tx
+        };
+        assert_ok!(provider.create_transaction::<serde_json::Value>(tx).await);
+        assert_eq!(mock.requests().len(), 3);
+    }
+
+    #[test]
+    fn backoff_should_grow_exponentially_up_to_max_backoff() {
+        let policy = RetryPolicy {
+            max_retries: 10,
+            initial_backoff: Duration::from_millis(100),
+            max_backoff: Duration::from_millis(1000),
+            jitter: false,
+        };
+        assert_eq!(policy.backoff(0), Duration::from_millis(100));
+        assert_eq!(policy.backoff(2), Duration::from_millis(400));
+        assert_eq!(policy.backoff(8), Duration::from_millis(1000));
+
+        let policy = RetryPolicy { jitter: true, ..policy };
+        for _ in 0..10 {
+            let backoff = policy.backoff(2);
+            assert!(backoff >= Duration::from_millis(200));
+            assert!(backoff <= Duration::from_millis(400));
+        }
+    }
+}
@@ -27,7 +27,7 @@ impl<M: Middleware, S: Signer> SignerMiddleware<M, S> {
 }
 
 #[async_trait]
-impl<M: Middleware, S: Signer + Debug + Sync + Send> Middleware for SignerMiddleware<M, S> {
+impl<M: Middleware, S: Signer + Debug> Middleware for SignerMiddleware<M, S> {
     type Provider = M::Provider;
 
     type Inner = M;
@@ -48,26 +48,129 @@ impl<M: Middleware, S: Signer + Debug + Sync + Send> Middleware for SignerMiddle
             tx.version = Version::new(self.inner().chainid());
         }
 
-        // TODO: Make it a middleware like ethers-rs
+        let from = match tx.from.take() {
+            Some(from) => from,
+            None => self.signer.address()?.clone(),
+        };
+
         // TODO: Is it a sane condition?
         if tx.nonce == u64::default() {
-            let balance = self.inner().get_balance(self.signer.address()).await?;
+            let balance = self.inner().get_balance(&from).await?;
             tx.nonce = balance.nonce + 1;
         }
 
-        let signature = self.sign_transaction(&tx)?;
-        tx.signature = Some(hex::encode(signature.to_bytes()));
+        if tx.gas_price == u128::default() {
+            tx.gas_price = self.inner().get_minimum_gas_price().await?;
+        }
+
+        tx.pub_key = Some(self.signer.public_key_for(&from)?.to_string());
+        tx.from = Some(from);
 
-        tx.pub_key = Some(self.signer.public_key().to_string().clone());
+        let signature = self.sign_transaction(&tx).await?;
+        tx.signature = Some(hex::encode(signature.to_bytes()));
 
         self.inner().create_transaction(tx).await
     }
 
-    fn sign_transaction(&self, tx: &CreateTransactionRequest) -> Result<crate::crypto::Signature, Error> {
-        Ok(self.signer.sign_transaction(tx))
+    async fn sign_transaction(&self, tx: &CreateTransactionRequest) -> Result<crate::crypto::Signature, Error> {
+        self.signer.sign_transaction(tx).await
+    }
+
+    async fn sign(&self, data: &[u8]) -> Result<crate::crypto::Signature, Error> {
+        self.signer.sign(data).await
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use claim::assert_matches;
+    use serde_json::{json, Value};
+
+    use crate::{
+        core::CreateTransactionRequest,
+        middlewares::Middleware,
+        providers::{mock::MockClient, Provider},
+        signers::{MultiAccountWallet, Signer},
+        Error,
+    };
+
+    use super::SignerMiddleware;
+
+    fn transfer() -> CreateTransactionRequest {
+        CreateTransactionRequest {
+            to_addr: "0x381f4008505e940ad7681ec3468a719060caf796".parse().unwrap(),
+            amount: 1,
+            gas_price: 2000000000,
+            gas_limit: 50,
+            ..Default::default()
+        }
+    }
+
+    #[tokio::test]
+    async fn transaction_should_be_signed_by_the_account_in_from_field() {
+        let mut wallet = MultiAccountWallet::default();
+        let default_account = wallet.create().unwrap();
+        let other_account = wallet.create().unwrap();
+
+        let mock = MockClient::default();
+        mock.push(json!({"balance": "1000", "nonce": 3}));
+        mock.push(json!({"TranID": "bdadfd994f452df803cc223d1f417b02830ac96dbe5edad1b9f8d58613f95206", "Info": ""}));
+        mock.push(json!({"TranID": "bdadfd994f452df803cc223d1f417b02830ac96dbe5edad1b9f8d58613f95206", "Info": ""}));
+
+        let provider = SignerMiddleware::new(Provider::new(mock.clone(), 222), wallet);
+        let tx = CreateTransactionRequest {
+            from: Some(other_account.address.clone()),
+            ..transfer()
+        };
+        provider.send_transaction_without_confirm::<Value>(tx).await.unwrap();
+
+        let tx = CreateTransactionRequest { nonce: 1, ..transfer() };
+        provider.send_transaction_without_confirm::<Value>(tx).await.unwrap();
+
+        let requests = mock.requests();
+        assert_eq!(requests[0].1, json!([other_account.address.to_string()]));
+        assert_eq!(requests[1].1[0]["pubKey"], other_account.public_key().unwrap().to_string());
+        assert_eq!(requests[1].1[0]["nonce"], 4);
+        assert_eq!(requests[2].1[0]["pubKey"], default_account.public_key().unwrap().to_string());
+    }
+
+    #[tokio::test]
+    async fn unset_gas_price_should_be_the_minimum_gas_price() {
+        let mut wallet = MultiAccountWallet::default();
+        wallet.create().unwrap();
+
+        let mock = MockClient::default();
+        mock.push("2000000000");
+        mock.push(json!({"TranID": "bdadfd994f452df803cc223d1f417b02830ac96dbe5edad1b9f8d58613f95206", "Info": ""}));
+
+        let provider = SignerMiddleware::new(Provider::new(mock.clone(), 222), wallet);
+        let tx = CreateTransactionRequest {
+            nonce: 1,
+            gas_price: 0,
+            ..transfer()
+        };
+        provider.send_transaction_without_confirm::<Value>(tx).await.unwrap();
+
+        let requests = mock.requests();
+        assert_eq!(requests[0].0, "GetMinimumGasPrice");
+        assert_eq!(requests[1].1[0]["gasPrice"], "2000000000");
     }
 
-    fn sign(&self, data: &[u8]) -> Result<crate::crypto::Signature, Error> {
-        Ok(self.signer.sign(data))
+    #[tokio::test]
+    async fn unknown_from_account_should_return_error() {
+        let mut wallet = MultiAccountWallet::default();
+        wallet.create().unwrap();
+
+        let provider = SignerMiddleware::new(Provider::new(MockClient::default(), 222), wallet);
+        let tx = CreateTransactionRequest {
+            nonce: 1,
+            from: Some("0x381f4008505e940AD7681EC3468a719060caF796".parse().unwrap()),
+            ..transfer()
+        };
+
+        assert_matches!(
+            provider.send_transaction_without_confirm::<Value>(tx).await,
+            Err(Error::AccountDoesNotExist(_))
+        );
     }
 }
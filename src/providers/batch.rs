@@ -0,0 +1,208 @@
+use std::fmt::Debug;
+
+use jsonrpsee::core::{
+    client::{BatchResponse, ClientT},
+    params::{ArrayParams, BatchRequestBuilder},
+    ClientError,
+};
+use serde::de::DeserializeOwned;
+
+use crate::{core::RPCMethod, Error};
+
+use super::{JsonRpcClient, Provider};
+
+/// A builder to compose a JSON-RPC batch request.
+///
+/// # Example
+/// ```no_run
+/// use jsonrpsee::rpc_params;
+/// use zilliqa_rs::core::RPCMethod;
+/// use zilliqa_rs::providers::{Http, Provider};
+///
+/// #[tokio::main]
+/// async fn main() -> anyhow::Result<()> {
+///     let provider = Provider::<Http>::try_from("http://127.0.0.1:5555")?.with_chain_id(222);
+///     let results = provider
+///         .batch()
+///         .add(RPCMethod::GetNumTxBlocks, rpc_params![])
+///         .add(RPCMethod::GetNumDsBlocks, rpc_params![])
+///         .send::<String>()
+///         .await?;
+///     for result in results {
+///         println!("{}", result?);
+///     }
+///     Ok(())
+/// }
+/// ```
+#[derive(Debug)]
+pub struct BatchBuilder<'a, P> {
+    provider: &'a Provider<P>,
+    requests: Vec<(String, ArrayParams)>,
+}
+
+impl<'a, P: JsonRpcClient> BatchBuilder<'a, P> {
+    pub(crate) fn new(provider: &'a Provider<P>) -> Self {
+        Self {
+            provider,
+            requests: vec![],
+        }
+    }
+
+    /// Adds a call to the batch.
+    pub fn add(mut self, rpc: RPCMethod, params: ArrayParams) -> Self {
+        self.requests.push((rpc.to_string(), params));
+        self
+    }
+
+    /// Number of calls in the batch.
+    pub fn len(&self) -> usize {
+        self.requests.len()
+    }
+
+    /// Checks if the batch has no calls.
+    pub fn is_empty(&self) -> bool {
+        self.requests.is_empty()
+    }
+
+    /// Sends the batch and returns the result of every call, in the order they were added.
+    pub async fn send<R: DeserializeOwned + Debug + Send>(self) -> Result<Vec<Result<R, Error>>, Error> {
+        self.provider.send_batch_request(self.requests).await
+    }
+}
+
+/// Sends the given requests using the batch support of a jsonrpsee client.
+pub(crate) async fn send_batch<C, R>(client: &C, requests: Vec<(String, ArrayParams)>) -> Result<Vec<Result<R, Error>>, Error>
+where
+    C: ClientT + Sync,
+    R: DeserializeOwned + Debug + Send,
+{
+    if requests.is_empty() {
+        return Ok(vec![]);
+    }
+
+    let mut batch = BatchRequestBuilder::new();
+    for (method, params) in &requests {
+        batch.insert(method, params.clone())?;
+    }
+
+    let response: BatchResponse<R> = client.batch_request(batch).await?;
+    Ok(response
+        .into_iter()
+        .map(|result| result.map_err(|e| Error::JsonRpcError(ClientError::Call(e.into_owned()))))
+        .collect())
+}
+
+#[cfg(test)]
+mod tests {
+    use jsonrpsee::rpc_params;
+    use serde_json::json;
+
+    use crate::{
+        core::{RPCMethod, TxHash},
+        middlewares::Middleware,
+        providers::{
+            mock::{fixture, serve_json_rpc, MockClient},
+            Http, Provider, RPCErrorCode,
+        },
+    };
+
+    #[tokio::test]
+    async fn batch_should_be_sent_in_one_request_and_keep_errors_per_item() {
+        let (url, bodies) = serve_json_rpc(|call| match call["method"].as_str() {
+            Some("GetNumTxBlocks") => json!("10"),
+            _ => json!({"error": {"code": -5, "message": "Txn Hash not Present"}}),
+        })
+        .await;
+        let provider = Provider::<Http>::try_from(url.as_str()).unwrap();
+
+        let results = provider
+            .batch()
+            .add(RPCMethod::GetNumTxBlocks, rpc_params![])
+            .add(
+                RPCMethod::GetTransaction,
+                rpc_params!["bdadfd994f452df803cc223d1f417b02830ac96dbe5edad1b9f8d58613f95206"],
+            )
+            .send::<String>()
+            .await
+            .unwrap();
+
+        assert_eq!(results.len(), 2);
+        assert_eq!(results[0].as_ref().unwrap(), "10");
+        assert_eq!(
+            RPCErrorCode::from_error(results[1].as_ref().unwrap_err()),
+            Some(RPCErrorCode::RpcInvalidAddressOrKey)
+        );
+
+        let bodies = bodies.lock().unwrap();
+        assert_eq!(bodies.len(), 1);
+        assert_eq!(bodies[0].as_array().unwrap().len(), 2);
+    }
+
+    #[tokio::test]
+    async fn big_batches_should_be_split_by_max_batch_size() {
+        let (url, bodies) =
+            serve_json_rpc(|call| fixture("get_tx_block", &[("/header/BlockNum", call["params"][0].clone())])).await;
+        let provider = Provider::<Http>::try_from(url.as_str()).unwrap().with_max_batch_size(2);
+
+        let blocks = provider.get_tx_blocks(10..15).await.unwrap();
+
+        let block_nums = blocks
+            .into_iter()
+            .map(|block| block.unwrap().header.block_num)
+            .collect::<Vec<_>>();
+        assert_eq!(block_nums, vec![10, 11, 12, 13, 14]);
+        let mut batch_sizes = bodies
+            .lock()
+            .unwrap()
+            .iter()
+            .map(|body| body.as_array().unwrap().len())
+            .collect::<Vec<_>>();
+        batch_sizes.sort();
+        assert_eq!(batch_sizes, vec![1, 2, 2]);
+    }
+
+    #[tokio::test]
+    async fn empty_batch_should_not_send_anything() {
+        let provider = Provider::<Http>::try_from("http://127.0.0.1:1").unwrap();
+        assert!(provider.batch().send::<String>().await.unwrap().is_empty());
+    }
+
+    #[tokio::test]
+    async fn get_transactions_should_keep_missing_transactions_as_errors() {
+        let mock = MockClient::default();
+        mock.push(json!({
+            "ID": "bdadfd994f452df803cc223d1f417b02830ac96dbe5edad1b9f8d58613f95206",
+            "version": "65537",
+            "nonce": "1",
+            "toAddr": "381f4008505e940ad7681ec3468a719060caf796",
+            "senderPubKey": "0x03050377b4d5a8cae8a1e7ee0f0d1c2a3c0e2db2c5bb8f0f5a9c71f0e0e1d4f3c2",
+            "amount": "1",
+            "signature": "0x00",
+            "receipt": {"cumulative_gas": "1", "epoch_num": "1", "success": true},
+            "gasPrice": "2000000000",
+            "gasLimit": "50"
+        }));
+        mock.push_error(jsonrpsee::core::ClientError::Call(jsonrpsee::types::ErrorObject::owned(
+            -5,
+            "Txn Hash not Present",
+            None::<()>,
+        )));
+
+        let provider = Provider::new(mock.clone(), 222);
+        let hashes: Vec<TxHash> = [
+            "bdadfd994f452df803cc223d1f417b02830ac96dbe5edad1b9f8d58613f95206",
+            "0000000000000000000000000000000000000000000000000000000000000000",
+        ]
+        .iter()
+        .map(|hash| hash.parse().unwrap())
+        .collect();
+        let results = provider.get_transactions(&hashes).await.unwrap();
+
+        assert!(results[0].is_ok());
+        assert!(results[1].is_err());
+        assert_eq!(
+            mock.requests()[1].1,
+            json!(["0000000000000000000000000000000000000000000000000000000000000000"])
+        );
+    }
+}
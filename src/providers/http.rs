@@ -1,6 +1,8 @@
+use std::fmt::Debug;
+
 use async_trait::async_trait;
 use jsonrpsee::{
-    core::{client::ClientT, traits::ToRpcParams},
+    core::{client::ClientT, params::ArrayParams, traits::ToRpcParams},
     http_client::{HttpClient, HttpClientBuilder},
 };
 use serde::de::DeserializeOwned;
@@ -8,7 +10,7 @@ use url::Url;
 
 use crate::Error;
 
-use super::JsonRpcClient;
+use super::{batch::send_batch, JsonRpcClient};
 
 /// HTTP Provider
 #[derive(Debug)]
@@ -30,4 +32,11 @@ impl JsonRpcClient for Http {
     async fn request<T: Send + Sync + ToRpcParams, R: DeserializeOwned>(&self, method: &str, params: T) -> Result<R, Error> {
         self.client.request(method, params).await.map_err(Error::JsonRpcError)
     }
+
+    async fn batch_request<R>(&self, requests: Vec<(String, ArrayParams)>) -> Result<Vec<Result<R, Error>>, Error>
+    where
+        R: DeserializeOwned + Debug + Send,
+    {
+        send_batch(&self.client, requests).await
+    }
 }
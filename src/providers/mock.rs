@@ -0,0 +1,191 @@
+use std::{
+    collections::VecDeque,
+    sync::{Arc, Mutex},
+};
+
+use async_trait::async_trait;
+use jsonrpsee::core::{traits::ToRpcParams, ClientError};
+use serde::{de::DeserializeOwned, Serialize};
+use serde_json::Value;
+use tokio::{
+    io::{AsyncReadExt, AsyncWriteExt},
+    net::{TcpListener, TcpStream},
+};
+
+use crate::{core::TransactionReceipt, Error};
+
+use super::JsonRpcClient;
+
+/// A JSON-RPC client that answers requests from a queue of canned responses and records every
+/// request it receives. Clones share the same queue.
+#[derive(Debug, Clone, Default)]
+pub(crate) struct MockClient {
+    responses: Arc<Mutex<VecDeque<Result<Value, ClientError>>>>,
+    requests: Arc<Mutex<Vec<(String, Value)>>>,
+}
+
+impl MockClient {
+    /// Queues a successful response.
+    pub fn push<T: Serialize>(&self, response: T) {
+        self.responses
+            .lock()
+            .unwrap()
+            .push_back(Ok(serde_json::to_value(response).unwrap()));
+    }
+
+    /// Queues an error response.
+    pub fn push_error(&self, error: ClientError) {
+        self.responses.lock().unwrap().push_back(Err(error));
+    }
+
+    /// Returns method names and params of the received requests.
+    pub fn requests(&self) -> Vec<(String, Value)> {
+        self.requests.lock().unwrap().clone()
+    }
+}
+
+#[async_trait]
+impl JsonRpcClient for MockClient {
+    async fn request<T: Send + Sync + ToRpcParams, R: DeserializeOwned>(&self, method: &str, params: T) -> Result<R, Error> {
+        let params = match params.to_rpc_params()? {
+            Some(params) => serde_json::from_str(params.get())?,
+            None => Value::Null,
+        };
+        self.requests.lock().unwrap().push((method.to_string(), params));
+
+        let response = self
+            .responses
+            .lock()
+            .unwrap()
+            .pop_front()
+            .unwrap_or_else(|| panic!("No response is queued for {method}"));
+
+        Ok(serde_json::from_value(response.map_err(Error::JsonRpcError)?)?)
+    }
+}
+
+/// Loads the response `tests/fixtures/{name}.json`, and overwrites the given fields, addressed by JSON
+/// pointers, e.g. `fixture("get_tx_block", &[("/header/BlockNum", json!("10"))])`.
+pub(crate) fn fixture(name: &str, fields: &[(&str, Value)]) -> Value {
+    let path = format!("{}/tests/fixtures/{name}.json", env!("CARGO_MANIFEST_DIR"));
+    let mut response: Value = serde_json::from_str(&std::fs::read_to_string(&path).unwrap()).unwrap();
+    for (pointer, value) in fields {
+        *response
+            .pointer_mut(pointer)
+            .unwrap_or_else(|| panic!("{name} has no field {pointer}")) = value.clone();
+    }
+    response
+}
+
+/// Loads the response `tests/fixtures/{name}.json` into the given type.
+pub(crate) fn typed_fixture<T: DeserializeOwned>(name: &str) -> T {
+    serde_json::from_value(fixture(name, &[])).unwrap()
+}
+
+/// Builds the receipt of a successful transaction, with the given fields added or overwritten, e.g.
+/// `receipt(json!({ "event_logs": [...] }))`.
+pub(crate) fn receipt(fields: Value) -> TransactionReceipt {
+    let mut receipt = serde_json::json!({ "cumulative_gas": "1000", "epoch_num": "12", "success": true });
+    for (field, value) in fields.as_object().expect("receipt fields must be an object") {
+        receipt[field] = value.clone();
+    }
+    serde_json::from_value(receipt).unwrap()
+}
+
+/// Starts a JSON-RPC server over HTTP on a random local port, which answers every call using the given
+/// handler. Batches are answered item by item. Returns the URL of the server and the bodies of the
+/// received HTTP requests.
+pub(crate) async fn serve_json_rpc<F>(handler: F) -> (String, Arc<Mutex<Vec<Value>>>)
+where
+    F: Fn(&Value) -> Value + Send + Sync + 'static,
+{
+    let listener = TcpListener::bind("127.0.0.1:0").await.unwrap();
+    let url = format!("http://{}", listener.local_addr().unwrap());
+    let bodies = Arc::new(Mutex::new(vec![]));
+    let handler = Arc::new(handler);
+
+    let received = bodies.clone();
+    tokio::spawn(async move {
+        while let Ok((socket, _)) = listener.accept().await {
+            tokio::spawn(serve_connection(socket, handler.clone(), received.clone()));
+        }
+    });
+
+    (url, bodies)
+}
+
+async fn serve_connection<F>(mut socket: TcpStream, handler: Arc<F>, bodies: Arc<Mutex<Vec<Value>>>)
+where
+    F: Fn(&Value) -> Value,
+{
+    let mut buffer = vec![];
+    loop {
+        let Some((header_len, content_len)) = read_headers(&mut socket, &mut buffer).await else {
+            return;
+        };
+        while buffer.len() < header_len + content_len {
+            if !read_chunk(&mut socket, &mut buffer).await {
+                return;
+            }
+        }
+
+        let body: Value = serde_json::from_slice(&buffer[header_len..header_len + content_len]).unwrap();
+        buffer.drain(..header_len + content_len);
+
+        let response = match &body {
+            Value::Array(calls) => Value::Array(calls.iter().map(|call| respond(call, handler.as_ref())).collect()),
+            call => respond(call, handler.as_ref()),
+        };
+        bodies.lock().unwrap().push(body);
+
+        let response = response.to_string();
+        let response = format!(
+            "HTTP/1.1 200 OK\r\nContent-Type: application/json\r\nContent-Length: {}\r\n\r\n{}",
+            response.len(),
+            response
+        );
+        if socket.write_all(response.as_bytes()).await.is_err() {
+            return;
+        }
+    }
+}
+
+/// Wraps the handler's answer to a call into a JSON-RPC response. Answers with an `error` field are
+/// sent as errors.
+fn respond<F: Fn(&Value) -> Value>(call: &Value, handler: &F) -> Value {
+    let answer = handler(call);
+    match answer.get("error") {
+        Some(error) => serde_json::json!({"jsonrpc": "2.0", "id": call["id"], "error": error}),
+        None => serde_json::json!({"jsonrpc": "2.0", "id": call["id"], "result": answer}),
+    }
+}
+
+/// Reads until the end of the HTTP headers and returns their length and the announced content length.
+async fn read_headers(socket: &mut TcpStream, buffer: &mut Vec<u8>) -> Option<(usize, usize)> {
+    loop {
+        if let Some(end) = buffer.windows(4).position(|window| window == b"\r\n\r\n") {
+            let headers = String::from_utf8_lossy(&buffer[..end]).to_lowercase();
+            let content_len = headers
+                .lines()
+                .find_map(|line| line.strip_prefix("content-length:"))
+                .map(|len| len.trim().parse().unwrap())
+                .unwrap_or(0);
+            return Some((end + 4, content_len));
+        }
+
+        if !read_chunk(socket, buffer).await {
+            return None;
+        }
+    }
+}
+
+async fn read_chunk(socket: &mut TcpStream, buffer: &mut Vec<u8>) -> bool {
+    let mut chunk = [0u8; 4096];
+    match socket.read(&mut chunk).await {
+        Ok(0) | Err(_) => false,
+        Ok(n) => {
+            buffer.extend_from_slice(&chunk[..n]);
+            true
+        }
+    }
+}
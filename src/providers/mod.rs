@@ -1,14 +1,23 @@
 //! Clients for interacting with zilliqa network.
 
+pub mod batch;
 pub mod http;
+#[cfg(test)]
+pub(crate) mod mock;
 pub mod provider;
+pub mod pubsub;
+pub(crate) mod stream;
+pub mod ws;
 
+pub use batch::BatchBuilder;
 pub use http::Http;
 pub use provider::Provider;
+pub use pubsub::{FromNotification, PubsubClient, SubscriptionStream};
+pub use ws::Ws;
 
 use crate::Error;
 use async_trait::async_trait;
-use jsonrpsee::core::traits::ToRpcParams;
+use jsonrpsee::core::{params::ArrayParams, traits::ToRpcParams};
 use serde::de::DeserializeOwned;
 use std::fmt::Debug;
 
@@ -21,8 +30,26 @@ pub trait JsonRpcClient: Debug + Send + Sync {
     where
         T: Debug + Send + Sync + ToRpcParams,
         R: DeserializeOwned + Send;
+
+    /// Sends the given requests in a single JSON-RPC batch.
+    ///
+    /// Results are returned in the order of the requests. A failure of the whole batch, e.g. a network
+    /// error, is returned as the outer error, while errors of individual requests are kept per item.
+    ///
+    /// The default implementation sends the requests one by one.
+    async fn batch_request<R>(&self, requests: Vec<(String, ArrayParams)>) -> Result<Vec<Result<R, Error>>, Error>
+    where
+        R: DeserializeOwned + Debug + Send,
+    {
+        let mut results = Vec::with_capacity(requests.len());
+        for (method, params) in requests {
+            results.push(self.request(&method, params).await);
+        }
+        Ok(results)
+    }
 }
 
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
 pub enum RPCErrorCode {
     // Standard JSON-RPC 2.0 errors
     // RPC_INVALID_REQUEST is internally mapped to HTTP_BAD_REQUEST (400).
@@ -49,3 +76,38 @@ pub enum RPCErrorCode {
     RpcInWarmup = -28,             // Client still warming up
     RpcMethodDeprecated = -32,     // RPC method is deprecated
 }
+
+impl RPCErrorCode {
+    /// Returns the code of the JSON-RPC error carried by the given error, if it's a known one.
+    pub fn from_error(error: &Error) -> Option<Self> {
+        match error {
+            Error::JsonRpcError(jsonrpsee::core::ClientError::Call(error)) => Self::try_from(error.code()).ok(),
+            _ => None,
+        }
+    }
+}
+
+impl TryFrom<i32> for RPCErrorCode {
+    type Error = i32;
+
+    fn try_from(code: i32) -> Result<Self, Self::Error> {
+        Ok(match code {
+            -32600 => Self::RpcInvalidRequest,
+            -32601 => Self::RpcMethodNotFound,
+            -32602 => Self::RpcInvalidParams,
+            -32603 => Self::RpcInternalError,
+            -32700 => Self::RpcParseError,
+            -1 => Self::RpcMiscError,
+            -3 => Self::RpcTypeError,
+            -5 => Self::RpcInvalidAddressOrKey,
+            -8 => Self::RpcInvalidParameter,
+            -20 => Self::RpcDatabaseError,
+            -22 => Self::RpcDeserializationError,
+            -25 => Self::RpcVerifyError,
+            -26 => Self::RpcVerifyRejected,
+            -28 => Self::RpcInWarmup,
+            -32 => Self::RpcMethodDeprecated,
+            _ => return Err(code),
+        })
+    }
+}
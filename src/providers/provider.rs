@@ -3,12 +3,14 @@ use crate::{
     core::{
         types::RPCMethod::{self, *},
         types::*,
-        TxHash,
+        BlockId, TxHash,
     },
 };
 use async_trait::async_trait;
+use futures::{stream, StreamExt, TryStreamExt};
 use jsonrpsee::{core::params::ArrayParams, rpc_params};
 use serde::de::DeserializeOwned;
+use std::{fmt::Debug, ops::Range};
 use url::Url;
 
 use crate::{
@@ -16,10 +18,11 @@ use crate::{
     crypto::Signature,
     middlewares::{signer::SignerMiddleware, Middleware},
     signers::Signer,
+    transaction::{PendingTransaction, SignedTransaction},
     Error,
 };
 
-use super::{Http, JsonRpcClient};
+use super::{BatchBuilder, Http, JsonRpcClient, PubsubClient, SubscriptionStream};
 
 /// # Example
 /// ## From a URL
@@ -72,6 +75,8 @@ use super::{Http, JsonRpcClient};
 pub struct Provider<P> {
     inner: P,
     chain_id: u16,
+    max_concurrent_requests: usize,
+    max_batch_size: usize,
 }
 
 impl<P: JsonRpcClient> Provider<P> {
@@ -80,6 +85,8 @@ impl<P: JsonRpcClient> Provider<P> {
         Self {
             inner: provider,
             chain_id,
+            max_concurrent_requests: 4,
+            max_batch_size: 50,
         }
     }
 
@@ -116,6 +123,31 @@ impl<P: JsonRpcClient> Provider<P> {
         self
     }
 
+    /// Sets how many requests the streaming helpers, e.g. [Middleware::stream_block_range], send at once.
+    /// It's 4 by default.
+    pub fn with_max_concurrent_requests(mut self, max_concurrent_requests: usize) -> Self {
+        self.max_concurrent_requests = max_concurrent_requests.max(1);
+        self
+    }
+
+    /// Maximum number of requests the streaming helpers send at once.
+    pub fn max_concurrent_requests(&self) -> usize {
+        self.max_concurrent_requests
+    }
+
+    /// Sets how many calls a single batch request holds at most. Bigger batches, e.g. the ones sent by
+    /// [Middleware::get_tx_blocks], are split into several requests, as nodes limit the size of batches.
+    /// It's 50 by default.
+    pub fn with_max_batch_size(mut self, max_batch_size: usize) -> Self {
+        self.max_batch_size = max_batch_size.max(1);
+        self
+    }
+
+    /// Maximum number of calls in a single batch request.
+    pub fn max_batch_size(&self) -> usize {
+        self.max_batch_size
+    }
+
     /// Sends a JSON-RPC method.
     ///
     /// You don't need to call this function directly.
@@ -123,6 +155,91 @@ impl<P: JsonRpcClient> Provider<P> {
     pub async fn send_request<T: Send + DeserializeOwned>(&self, rpc: RPCMethod, params: ArrayParams) -> Result<T, Error> {
         self.inner.request(&rpc.to_string(), params).await
     }
+
+    /// Creates a builder to send many JSON-RPC methods in a batch request. Batches bigger than
+    /// [Provider::max_batch_size] are split into several requests.
+    pub fn batch(&self) -> BatchBuilder<'_, P> {
+        BatchBuilder::new(self)
+    }
+
+    /// Sends the given JSON-RPC methods in batch requests of at most [Provider::max_batch_size] calls. Batches
+    /// are sent concurrently, at most [Provider::max_concurrent_requests] at once.
+    ///
+    /// You don't need to call this function directly, use [Provider::batch] instead.
+    pub async fn send_batch_request<T: Send + Debug + DeserializeOwned>(
+        &self,
+        requests: Vec<(String, ArrayParams)>,
+    ) -> Result<Vec<Result<T, Error>>, Error> {
+        if requests.len() <= self.max_batch_size {
+            return self.inner.batch_request(requests).await;
+        }
+
+        let batches = requests
+            .chunks(self.max_batch_size)
+            .map(|batch| self.inner.batch_request(batch.to_vec()))
+            .collect::<Vec<_>>();
+        let results = stream::iter(batches)
+            .buffered(self.max_concurrent_requests)
+            .try_collect::<Vec<_>>()
+            .await?;
+        Ok(results.into_iter().flatten().collect())
+    }
+
+    /// Resolves a tx block ID to a block number, as zilliqa RPC methods only accept block numbers.
+    pub(super) async fn tx_block_number(&self, block: BlockId) -> Result<u64, Error> {
+        match block {
+            BlockId::Number(number) => Ok(number),
+            BlockId::Latest => Ok(self.get_num_tx_blocks().await?.saturating_sub(1)),
+        }
+    }
+
+    /// Resolves a DS block ID to a block number, as zilliqa RPC methods only accept block numbers.
+    async fn ds_block_number(&self, block: BlockId) -> Result<u64, Error> {
+        match block {
+            BlockId::Number(number) => Ok(number),
+            BlockId::Latest => Ok(self.get_num_ds_blocks().await?.saturating_sub(1)),
+        }
+    }
+
+    /// Broadcasts a transaction signed offline, e.g. using [LocalWallet::sign_request].
+    ///
+    /// [LocalWallet::sign_request]: crate::signers::LocalWallet::sign_request
+    pub async fn send_raw(&self, tx: &SignedTransaction) -> Result<PendingTransaction<'_, P>, Error> {
+        let response: CreateTransactionResponse = self.create_transaction(tx.request().clone()).await?;
+        Ok(PendingTransaction::new(response.tran_id, self))
+    }
+}
+
+impl<P: PubsubClient> Provider<P> {
+    /// Subscribes to new tx blocks.
+    ///
+    /// # Example
+    /// ```no_run
+    /// use zilliqa_rs::providers::{Provider, Ws};
+    /// use tokio_stream::StreamExt;
+    ///
+    /// #[tokio::main]
+    /// async fn main() -> anyhow::Result<()> {
+    ///     let provider = Provider::<Ws>::connect("wss://api-ws.zilliqa.com").await?;
+    ///     let mut blocks = provider.subscribe_new_blocks().await?;
+    ///     while let Some(block) = blocks.next().await {
+    ///         println!("{}", block?.header.block_num);
+    ///     }
+    ///     Ok(())
+    /// }
+    /// ```
+    pub async fn subscribe_new_blocks(&self) -> Result<SubscriptionStream<TxBlock>, Error> {
+        self.inner.subscribe(SubscriptionQuery::NewBlock).await
+    }
+
+    /// Subscribes to the events emitted by the given contracts.
+    pub async fn subscribe_event_logs(&self, addresses: &[ZilAddress]) -> Result<SubscriptionStream<EventLogEntry>, Error> {
+        self.inner
+            .subscribe(SubscriptionQuery::EventLog {
+                addresses: addresses.to_vec(),
+            })
+            .await
+    }
 }
 
 impl TryFrom<&str> for Provider<Http> {
@@ -159,7 +276,11 @@ impl<P: JsonRpcClient> Middleware for Provider<P> {
         self.chain_id
     }
 
-    fn sign_transaction(&self, _tx: &CreateTransactionRequest) -> Result<Signature, Error> {
+    async fn sign(&self, _data: &[u8]) -> Result<Signature, Error> {
+        Err(Error::NoSignerSpecified)
+    }
+
+    async fn sign_transaction(&self, _tx: &CreateTransactionRequest) -> Result<Signature, Error> {
         Err(Error::NoSignerSpecified)
     }
 
@@ -182,6 +303,16 @@ impl<P: JsonRpcClient> Middleware for Provider<P> {
         Ok(self.send_request(GetTransaction, rpc_params![tx_hash.to_string()]).await?)
     }
 
+    async fn get_transactions(&self, tx_hashes: &[TxHash]) -> Result<Vec<Result<GetTransactionResponse, Error>>, Error> {
+        tx_hashes
+            .iter()
+            .fold(self.batch(), |batch, tx_hash| {
+                batch.add(GetTransaction, rpc_params![tx_hash.to_string()])
+            })
+            .send()
+            .await
+    }
+
     async fn get_soft_confirmed_transaction(&self, tx_hash: &TxHash) -> Result<GetTransactionResponse, Error> {
         Ok(self
             .send_request(GetSoftConfirmedTransaction, rpc_params![tx_hash.to_string()])
@@ -202,32 +333,60 @@ impl<P: JsonRpcClient> Middleware for Provider<P> {
         Ok(self.send_request(GetCurrentDsComm, rpc_params![]).await?)
     }
 
-    async fn get_ds_block(&self, lock_num: &str) -> Result<DsBlock, Error> {
-        Ok(self.send_request(GetDsBlock, rpc_params![lock_num]).await?)
+    async fn get_ds_block(&self, block: BlockId) -> Result<DsBlock, Error> {
+        match block {
+            BlockId::Latest => self.get_latest_ds_block().await,
+            _ => {
+                let block_num = self.ds_block_number(block).await?;
+                Ok(self.send_request(GetDsBlock, rpc_params![block_num.to_string()]).await?)
+            }
+        }
     }
 
-    async fn get_ds_block_verbose(&self, lock_num: &str) -> Result<DsBlockVerbose, Error> {
-        Ok(self.send_request(GetDsBlockVerbose, rpc_params![lock_num]).await?)
+    async fn get_ds_block_verbose(&self, block: BlockId) -> Result<DsBlockVerbose, Error> {
+        let block_num = self.ds_block_number(block).await?;
+        Ok(self
+            .send_request(GetDsBlockVerbose, rpc_params![block_num.to_string()])
+            .await?)
     }
 
     async fn ds_block_listing(&self, max: u32) -> Result<BlockList, Error> {
         Ok(self.send_request(DsBlockListing, rpc_params![max]).await?)
     }
 
-    async fn get_tx_block(&self, block_num: &str) -> Result<TxBlock, Error> {
-        Ok(self.send_request(GetTxBlock, rpc_params![block_num]).await?)
+    async fn get_tx_block(&self, block: BlockId) -> Result<TxBlock, Error> {
+        match block {
+            BlockId::Latest => self.get_latest_tx_block().await,
+            _ => {
+                let block_num = self.tx_block_number(block).await?;
+                Ok(self.send_request(GetTxBlock, rpc_params![block_num.to_string()]).await?)
+            }
+        }
     }
 
-    async fn get_tx_block_verbose(&self, block_num: &str) -> Result<TxBlockVerbose, Error> {
-        Ok(self.send_request(GetTxBlockVerbose, rpc_params![block_num]).await?)
+    async fn get_tx_blocks(&self, block_nums: Range<u64>) -> Result<Vec<Result<TxBlock, Error>>, Error> {
+        block_nums
+            .fold(self.batch(), |batch, block_num| {
+                batch.add(GetTxBlock, rpc_params![block_num.to_string()])
+            })
+            .send()
+            .await
+    }
+
+    async fn get_tx_block_verbose(&self, block: BlockId) -> Result<TxBlockVerbose, Error> {
+        let block_num = self.tx_block_number(block).await?;
+        Ok(self
+            .send_request(GetTxBlockVerbose, rpc_params![block_num.to_string()])
+            .await?)
     }
 
     async fn tx_block_listing(&self, max: u32) -> Result<BlockList, Error> {
         Ok(self.send_request(TxBlockListing, rpc_params![max]).await?)
     }
 
-    async fn get_miner_info(&self, ds_block_number: &str) -> Result<MinerInfo, Error> {
-        Ok(self.send_request(GetMinerInfo, rpc_params![ds_block_number]).await?)
+    async fn get_miner_info(&self, ds_block: BlockId) -> Result<MinerInfo, Error> {
+        let block_num = self.ds_block_number(ds_block).await?;
+        Ok(self.send_request(GetMinerInfo, rpc_params![block_num.to_string()]).await?)
     }
 
     async fn get_blockchain_info(&self) -> Result<BlockchainInfo, Error> {
@@ -246,8 +405,9 @@ impl<P: JsonRpcClient> Middleware for Provider<P> {
         Ok(self.send_request(GetLatestDsBlock, rpc_params![]).await?)
     }
 
-    async fn get_num_ds_blocks(&self) -> Result<String, Error> {
-        Ok(self.send_request(GetNumDsBlocks, rpc_params![]).await?)
+    async fn get_num_ds_blocks(&self) -> Result<u64, Error> {
+        let value: String = self.send_request(GetNumDsBlocks, rpc_params![]).await?;
+        Ok(value.parse()?)
     }
 
     async fn get_ds_block_rate(&self) -> Result<f32, Error> {
@@ -258,28 +418,32 @@ impl<P: JsonRpcClient> Middleware for Provider<P> {
         Ok(self.send_request(GetLatestTxBlock, rpc_params![]).await?)
     }
 
-    async fn get_num_tx_blocks(&self) -> Result<String, Error> {
-        Ok(self.send_request(GetNumTxBlocks, rpc_params![]).await?)
+    async fn get_num_tx_blocks(&self) -> Result<u64, Error> {
+        let value: String = self.send_request(GetNumTxBlocks, rpc_params![]).await?;
+        Ok(value.parse()?)
     }
 
     async fn get_tx_block_rate(&self) -> Result<f32, Error> {
         Ok(self.send_request(GetTxBlockRate, rpc_params![]).await?)
     }
 
-    async fn get_num_transactions(&self) -> Result<String, Error> {
-        Ok(self.send_request(GetNumTransactions, rpc_params![]).await?)
+    async fn get_num_transactions(&self) -> Result<u64, Error> {
+        let value: String = self.send_request(GetNumTransactions, rpc_params![]).await?;
+        Ok(value.parse()?)
     }
 
     async fn get_transaction_rate(&self) -> Result<f32, Error> {
         Ok(self.send_request(GetTransactionRate, rpc_params![]).await?)
     }
 
-    async fn get_current_mini_epoch(&self) -> Result<String, Error> {
-        Ok(self.send_request(GetCurrentMiniEpoch, rpc_params![]).await?)
+    async fn get_current_mini_epoch(&self) -> Result<u64, Error> {
+        let value: String = self.send_request(GetCurrentMiniEpoch, rpc_params![]).await?;
+        Ok(value.parse()?)
     }
 
-    async fn get_current_ds_epoch(&self) -> Result<String, Error> {
-        Ok(self.send_request(GetCurrentDsEpoch, rpc_params![]).await?)
+    async fn get_current_ds_epoch(&self) -> Result<u64, Error> {
+        let value: String = self.send_request(GetCurrentDsEpoch, rpc_params![]).await?;
+        Ok(value.parse()?)
     }
 
     async fn get_prev_difficulty(&self) -> Result<u32, Error> {
@@ -306,36 +470,59 @@ impl<P: JsonRpcClient> Middleware for Provider<P> {
         Ok(self.send_request(GetRecentTransactions, rpc_params![]).await?)
     }
 
-    async fn get_transactions_for_tx_block(&self, tx_block: &str) -> Result<Vec<Vec<String>>, Error> {
-        Ok(self.send_request(GetTransactionsForTxBlock, rpc_params![tx_block]).await?)
+    async fn get_transactions_for_tx_block(&self, tx_block: BlockId) -> Result<Vec<Vec<TxHash>>, Error> {
+        let block_num = self.tx_block_number(tx_block).await?;
+        Ok(self
+            .send_request(GetTransactionsForTxBlock, rpc_params![block_num.to_string()])
+            .await?)
     }
 
-    async fn get_txn_bodies_for_tx_block_ex(&self, tx_block: &str, page_num: &str) -> Result<TxnBodiesForTxBlockEx, Error> {
+    async fn get_txn_bodies_for_tx_block_ex(&self, tx_block: BlockId, page_num: u32) -> Result<TxnBodiesForTxBlockEx, Error> {
+        let block_num = self.tx_block_number(tx_block).await?;
         Ok(self
-            .send_request(GetTxnBodiesForTxBlockEx, rpc_params![tx_block, page_num])
+            .send_request(
+                GetTxnBodiesForTxBlockEx,
+                rpc_params![block_num.to_string(), page_num.to_string()],
+            )
             .await?)
     }
 
-    async fn get_txn_bodies_for_tx_block(&self, tx_block: &str) -> Result<Vec<GetTransactionResponse>, Error> {
-        Ok(self.send_request(GetTxnBodiesForTxBlock, rpc_params![tx_block]).await?)
+    async fn get_txn_bodies_for_tx_block(&self, tx_block: BlockId) -> Result<Vec<GetTransactionResponse>, Error> {
+        let block_num = self.tx_block_number(tx_block).await?;
+        Ok(self
+            .send_request(GetTxnBodiesForTxBlock, rpc_params![block_num.to_string()])
+            .await?)
     }
 
-    async fn get_transactions_for_tx_block_ex(&self, tx_block: &str, page_num: &str) -> Result<TransactionsForTxBlockEx, Error> {
+    async fn get_transactions_for_tx_block_ex(
+        &self,
+        tx_block: BlockId,
+        page_num: u32,
+    ) -> Result<TransactionsForTxBlockEx, Error> {
+        let block_num = self.tx_block_number(tx_block).await?;
         Ok(self
-            .send_request(GetTransactionsForTxBlockEx, rpc_params![tx_block, page_num])
+            .send_request(
+                GetTransactionsForTxBlockEx,
+                rpc_params![block_num.to_string(), page_num.to_string()],
+            )
             .await?)
     }
 
-    async fn get_num_txns_tx_epoch(&self, epoch: &str) -> Result<String, Error> {
-        Ok(self.send_request(GetNumTxnsTxEpoch, rpc_params![epoch]).await?)
+    async fn get_num_txns_tx_epoch(&self, epoch: BlockId) -> Result<u64, Error> {
+        let epoch = self.tx_block_number(epoch).await?;
+        let value: String = self.send_request(GetNumTxnsTxEpoch, rpc_params![epoch.to_string()]).await?;
+        Ok(value.parse()?)
     }
 
-    async fn get_num_txns_ds_epoch(&self, epoch: &str) -> Result<String, Error> {
-        Ok(self.send_request(GetNumTxnsDsEpoch, rpc_params![epoch]).await?)
+    async fn get_num_txns_ds_epoch(&self, epoch: BlockId) -> Result<u64, Error> {
+        let epoch = self.ds_block_number(epoch).await?;
+        let value: String = self.send_request(GetNumTxnsDsEpoch, rpc_params![epoch.to_string()]).await?;
+        Ok(value.parse()?)
     }
 
-    async fn get_minimum_gas_price(&self) -> Result<String, Error> {
-        Ok(self.send_request(GetMinimumGasPrice, rpc_params![]).await?)
+    async fn get_minimum_gas_price(&self) -> Result<u128, Error> {
+        let value: String = self.send_request(GetMinimumGasPrice, rpc_params![]).await?;
+        Ok(value.parse()?)
     }
 
     async fn get_contract_address_from_transaction_id(&self, tx_hash: &TxHash) -> Result<String, Error> {
@@ -380,10 +567,65 @@ impl<P: JsonRpcClient> Middleware for Provider<P> {
         &self,
         contract_address: &ZilAddress,
         hash: &str,
-        tx_block: &str,
+        tx_block: BlockId,
     ) -> Result<serde_json::Value, Error> {
+        let block_num = self.tx_block_number(tx_block).await?;
         Ok(self
-            .send_request(GetStateProof, rpc_params![contract_address, hash, tx_block])
+            .send_request(GetStateProof, rpc_params![contract_address, hash, block_num.to_string()])
             .await?)
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use serde_json::json;
+
+    use crate::{
+        core::BlockId,
+        middlewares::Middleware,
+        providers::{
+            mock::{fixture, MockClient},
+            Provider,
+        },
+    };
+
+    fn tx_block(block_num: &str) -> serde_json::Value {
+        fixture("get_tx_block", &[("/header/BlockNum", json!(block_num))])
+    }
+
+    #[tokio::test]
+    async fn latest_tx_block_should_be_fetched_using_get_latest_tx_block() {
+        let mock = MockClient::default();
+        mock.push(tx_block("1002353"));
+
+        let provider = Provider::new(mock.clone(), 222);
+        let block = provider.get_tx_block(BlockId::Latest).await.unwrap();
+
+        assert_eq!(block.header.block_num, 1002353);
+        assert_eq!(mock.requests()[0].0, "GetLatestTxBlock");
+    }
+
+    #[tokio::test]
+    async fn block_number_should_be_sent_as_a_string() {
+        let mock = MockClient::default();
+        mock.push(tx_block("42"));
+
+        let provider = Provider::new(mock.clone(), 222);
+        provider.get_tx_block(42.into()).await.unwrap();
+
+        assert_eq!(mock.requests()[0], ("GetTxBlock".to_string(), json!(["42"])));
+    }
+
+    #[tokio::test]
+    async fn latest_should_be_resolved_for_methods_taking_block_numbers() {
+        let mock = MockClient::default();
+        mock.push("1002354");
+        mock.push("3");
+
+        let provider = Provider::new(mock.clone(), 222);
+        provider.get_num_txns_tx_epoch(BlockId::Latest).await.unwrap();
+
+        assert_eq!(mock.requests()[0].0, "GetNumTxBlocks");
+        assert_eq!(mock.requests()[1], ("GetNumTxnsTxEpoch".to_string(), json!(["1002353"])));
+    }
+}
@@ -0,0 +1,167 @@
+use std::{
+    pin::Pin,
+    task::{Context, Poll},
+};
+
+use async_trait::async_trait;
+use tokio::sync::mpsc;
+use tokio_stream::{wrappers::ReceiverStream, Stream};
+
+use crate::{
+    core::{EventLogEntry, SubscriptionQuery, SubscriptionValue, TxBlock},
+    Error,
+};
+
+use super::JsonRpcClient;
+
+/// A client that can subscribe to the websocket topics of a zilliqa node.
+#[async_trait]
+pub trait PubsubClient: JsonRpcClient {
+    /// Subscribes to the given topic and returns a stream of the items extracted from its notifications.
+    async fn subscribe<T: FromNotification>(&self, query: SubscriptionQuery) -> Result<SubscriptionStream<T>, Error>;
+}
+
+/// Types that can be extracted out of a websocket notification.
+pub trait FromNotification: Sized + Send + 'static {
+    fn from_notification(value: SubscriptionValue) -> Vec<Self>;
+}
+
+impl FromNotification for TxBlock {
+    fn from_notification(value: SubscriptionValue) -> Vec<Self> {
+        match value {
+            SubscriptionValue::NewBlock(notification) => vec![notification.tx_block],
+            _ => vec![],
+        }
+    }
+}
+
+impl FromNotification for EventLogEntry {
+    fn from_notification(value: SubscriptionValue) -> Vec<Self> {
+        match value {
+            SubscriptionValue::EventLog(contracts) => contracts
+                .into_iter()
+                .flat_map(|contract| {
+                    let address = contract.address;
+                    contract.event_logs.into_iter().map(move |event| EventLogEntry {
+                        address: address.clone(),
+                        _eventname: event._eventname,
+                        params: event.params,
+                    })
+                })
+                .collect(),
+            _ => vec![],
+        }
+    }
+}
+
+/// A stream of items pushed by a subscription.
+///
+/// The subscription is cancelled once the stream is dropped.
+#[derive(Debug)]
+pub struct SubscriptionStream<T> {
+    inner: ReceiverStream<Result<T, Error>>,
+}
+
+impl<T> SubscriptionStream<T> {
+    /// Creates a new stream out of the receiving half of a subscription channel.
+    pub fn new(receiver: mpsc::Receiver<Result<T, Error>>) -> Self {
+        Self {
+            inner: ReceiverStream::new(receiver),
+        }
+    }
+
+    /// Cancels the subscription.
+    pub fn unsubscribe(self) {
+        drop(self)
+    }
+}
+
+impl<T> Stream for SubscriptionStream<T> {
+    type Item = Result<T, Error>;
+
+    fn poll_next(mut self: Pin<&mut Self>, cx: &mut Context<'_>) -> Poll<Option<Self::Item>> {
+        Pin::new(&mut self.inner).poll_next(cx)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::core::{EventLogEntry, SubscriptionValue, TxBlock};
+
+    use super::FromNotification;
+
+    #[test]
+    fn event_log_notification_should_be_flattened_into_entries() {
+        let value: SubscriptionValue = serde_json::from_str(
+            r#"{
+                "query": "EventLog",
+                "value": [
+                    {
+                        "address": "0x0000000000000000000000000000000000000001",
+                        "event_logs": [
+                            {"_eventname": "foo1", "params": [{"vname": "bar1", "type": "String", "value": "abc"}]},
+                            {"_eventname": "foo2", "params": []}
+                        ]
+                    },
+                    {
+                        "address": "0x0000000000000000000000000000000000000002",
+                        "event_logs": [{"_eventname": "foo3", "params": []}]
+                    }
+                ]
+            }"#,
+        )
+        .unwrap();
+
+        let entries = EventLogEntry::from_notification(value);
+
+        assert_eq!(entries.len(), 3);
+        assert_eq!(entries[0]._eventname, "foo1");
+        assert_eq!(entries[0].address.to_string(), "0x0000000000000000000000000000000000000001");
+        assert_eq!(entries[0].params[0].vname, "bar1");
+        assert_eq!(entries[2]._eventname, "foo3");
+        assert_eq!(entries[2].address.to_string(), "0x0000000000000000000000000000000000000002");
+    }
+
+    #[test]
+    fn new_block_notification_should_yield_the_tx_block() {
+        let value: SubscriptionValue = serde_json::from_str(
+            r#"{
+                "query": "NewBlock",
+                "value": {
+                    "TxBlock": {
+                        "body": {
+                            "BlockHash": "2ba3e2bab02e4e9e4e21c4fe3e6bbba8cf2f5a3e2bd3b35a9d4c81c7f18a55be",
+                            "HeaderSign": "00",
+                            "MicroBlockInfos": []
+                        },
+                        "header": {
+                            "BlockNum": "1000",
+                            "DSBlockNum": "11",
+                            "GasLimit": "1",
+                            "GasUsed": "0",
+                            "MbInfoHash": "00",
+                            "MinerPubKey": "0x00",
+                            "NumMicroBlocks": 0,
+                            "NumPages": 0,
+                            "NumTxns": 0,
+                            "PrevBlockHash": "00",
+                            "Rewards": "0",
+                            "StateDeltaHash": "00",
+                            "StateRootHash": "00",
+                            "Timestamp": "1606443830834512",
+                            "TxnFees": "0",
+                            "Version": 1
+                        }
+                    },
+                    "TxHashes": [[]]
+                }
+            }"#,
+        )
+        .unwrap();
+
+        let blocks = TxBlock::from_notification(value);
+
+        assert_eq!(blocks.len(), 1);
+        assert_eq!(blocks[0].header.block_num, 1000);
+    }
+}
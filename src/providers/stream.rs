@@ -0,0 +1,219 @@
+use std::ops::Range;
+
+use futures::{
+    future,
+    stream::{self, BoxStream},
+    StreamExt, TryStreamExt,
+};
+
+use crate::{
+    core::{BlockId, GetTransactionResponse},
+    middlewares::Middleware,
+    Error,
+};
+
+/// Streams the bodies of all transactions of a tx block, fetching at most `concurrency` pages at once.
+/// The header of the block is fetched first, to skip the pages of blocks without transactions.
+///
+/// It's the implementation of [Middleware::stream_txn_bodies], so every call goes through the whole middleware
+/// stack, e.g. to be retried.
+pub(crate) fn txn_bodies<M: Middleware + ?Sized>(
+    middleware: &M,
+    block: BlockId,
+    concurrency: usize,
+) -> BoxStream<'_, Result<GetTransactionResponse, Error>> {
+    stream::once(async move {
+        // The header resolves `Latest` once, so all pages belong to the same block.
+        let header = middleware.get_tx_block(block).await?.header;
+        Ok::<_, Error>(block_txn_bodies(middleware, header.block_num, header.num_txns, concurrency))
+    })
+    .try_flatten()
+    .boxed()
+}
+
+/// Streams the bodies of the `num_txns` transactions of a tx block whose header is already fetched.
+///
+/// Nodes return an error for the bodies of a block without transactions, so nothing is fetched for them.
+pub(crate) fn block_txn_bodies<M: Middleware + ?Sized>(
+    middleware: &M,
+    block_num: u64,
+    num_txns: u32,
+    concurrency: usize,
+) -> BoxStream<'_, Result<GetTransactionResponse, Error>> {
+    if num_txns == 0 {
+        return stream::empty().boxed();
+    }
+
+    stream::once(async move {
+        let first = middleware.get_txn_bodies_for_tx_block_ex(block_num.into(), 0).await?;
+        let rest = stream::iter(1..first.num_pages)
+            .map(move |page| middleware.get_txn_bodies_for_tx_block_ex(block_num.into(), page))
+            .buffered(concurrency);
+        Ok::<_, Error>(stream::once(future::ready(Ok(first))).chain(rest))
+    })
+    .try_flatten()
+    .map_ok(|page| stream::iter(page.transactions.into_iter().map(Ok::<_, Error>)))
+    .try_flatten()
+    .boxed()
+}
+
+/// Streams the bodies of all transactions of the tx blocks in the given range, fetching at most `concurrency`
+/// blocks at once.
+///
+/// It's the implementation of [Middleware::stream_block_range].
+pub(crate) fn block_range<M: Middleware + ?Sized>(
+    middleware: &M,
+    blocks: Range<u64>,
+    concurrency: usize,
+) -> BoxStream<'_, Result<GetTransactionResponse, Error>> {
+    stream::iter(blocks)
+        // Blocks are already fetched concurrently, so pages of a block are fetched one by one.
+        .map(move |block_num| txn_bodies(middleware, block_num.into(), 1).try_collect::<Vec<_>>())
+        .buffered(concurrency)
+        .map_ok(|transactions| stream::iter(transactions.into_iter().map(Ok::<_, Error>)))
+        .try_flatten()
+        .boxed()
+}
+
+#[cfg(test)]
+mod tests {
+    use std::time::Duration;
+
+    use futures::TryStreamExt;
+    use jsonrpsee::core::ClientError;
+    use serde_json::{json, Value};
+
+    use crate::{
+        core::BlockId,
+        middlewares::{
+            retry::{RetryMiddleware, RetryPolicy},
+            Middleware,
+        },
+        providers::{
+            mock::{fixture, MockClient},
+            Provider,
+        },
+    };
+
+    fn tx(id: u8) -> Value {
+        fixture("get_transaction", &[("/ID", json!(format!("{id:064x}")))])
+    }
+
+    fn page(curr_page: u32, num_pages: u32, ids: &[u8]) -> Value {
+        json!({
+            "CurrPage": curr_page,
+            "NumPages": num_pages,
+            "Transactions": ids.iter().map(|id| tx(*id)).collect::<Vec<_>>(),
+        })
+    }
+
+    fn tx_block(num_txns: u32) -> Value {
+        fixture("get_tx_block", &[("/header/NumTxns", json!(num_txns))])
+    }
+
+    fn ids(transactions: Vec<crate::core::GetTransactionResponse>) -> Vec<String> {
+        transactions
+            .into_iter()
+            .map(|tx| tx.id.to_string()[62..].to_string())
+            .collect()
+    }
+
+    fn methods(mock: &MockClient) -> Vec<String> {
+        mock.requests().into_iter().map(|(method, _)| method).collect()
+    }
+
+    #[tokio::test]
+    async fn stream_txn_bodies_should_walk_all_pages_in_order() {
+        let mock = MockClient::default();
+        mock.push(tx_block(5));
+        mock.push(page(0, 3, &[1, 2]));
+        mock.push(page(1, 3, &[3, 4]));
+        mock.push(page(2, 3, &[5]));
+
+        let provider = Provider::new(mock.clone(), 222);
+        let transactions: Vec<_> = provider.stream_txn_bodies(BlockId::Number(7)).try_collect().await.unwrap();
+
+        assert_eq!(ids(transactions), vec!["01", "02", "03", "04", "05"]);
+        let pages: Vec<_> = mock.requests().into_iter().skip(1).map(|(_, params)| params).collect();
+        assert_eq!(
+            pages,
+            vec![json!(["1002353", "0"]), json!(["1002353", "1"]), json!(["1002353", "2"])]
+        );
+    }
+
+    #[tokio::test]
+    async fn stream_txn_bodies_should_be_empty_for_blocks_without_transactions() {
+        let mock = MockClient::default();
+        mock.push(tx_block(0));
+
+        let provider = Provider::new(mock.clone(), 222);
+        let transactions: Vec<_> = provider.stream_txn_bodies(BlockId::Number(7)).try_collect().await.unwrap();
+
+        assert!(transactions.is_empty());
+        assert_eq!(methods(&mock), vec!["GetTxBlock"]);
+    }
+
+    #[tokio::test]
+    async fn stream_block_range_should_skip_empty_blocks() {
+        let mock = MockClient::default();
+        mock.push(tx_block(2));
+        mock.push(page(0, 1, &[1, 2]));
+        mock.push(tx_block(0));
+        mock.push(tx_block(1));
+        mock.push(page(0, 1, &[3]));
+
+        let provider = Provider::new(mock.clone(), 222).with_max_concurrent_requests(1);
+        let transactions: Vec<_> = provider.stream_block_range(10..13).try_collect().await.unwrap();
+
+        assert_eq!(ids(transactions), vec!["01", "02", "03"]);
+        assert_eq!(
+            methods(&mock),
+            vec![
+                "GetTxBlock",
+                "GetTxnBodiesForTxBlockEx",
+                "GetTxBlock",
+                "GetTxBlock",
+                "GetTxnBodiesForTxBlockEx"
+            ]
+        );
+    }
+
+    #[tokio::test]
+    async fn streams_should_go_through_the_middleware_stack() {
+        let mock = MockClient::default();
+        mock.push(tx_block(1));
+        mock.push_error(ClientError::RequestTimeout);
+        mock.push(page(0, 1, &[1]));
+
+        let middleware = RetryMiddleware::new(Provider::new(mock.clone(), 222)).with_policy(RetryPolicy {
+            max_retries: 1,
+            initial_backoff: Duration::from_millis(1),
+            max_backoff: Duration::from_millis(1),
+            jitter: false,
+        });
+        let transactions: Vec<_> = middleware.stream_block_range(10..11).try_collect().await.unwrap();
+
+        assert_eq!(ids(transactions), vec!["01"]);
+        assert_eq!(
+            methods(&mock),
+            vec!["GetTxBlock", "GetTxnBodiesForTxBlockEx", "GetTxnBodiesForTxBlockEx"]
+        );
+    }
+
+    #[tokio::test]
+    async fn stream_should_end_with_the_error_of_a_failed_request() {
+        let mock = MockClient::default();
+        mock.push(tx_block(2));
+        mock.push(page(0, 2, &[1]));
+        mock.push_error(jsonrpsee::core::ClientError::Call(jsonrpsee::types::ErrorObject::owned(
+            -1,
+            "Internal error",
+            None::<()>,
+        )));
+
+        let provider = Provider::new(mock.clone(), 222);
+        let result: Result<Vec<_>, _> = provider.stream_txn_bodies(BlockId::Number(7)).try_collect().await;
+
+        assert!(result.is_err());
+    }
+}
@@ -0,0 +1,179 @@
+use std::fmt::Debug;
+
+use async_trait::async_trait;
+use jsonrpsee::{
+    client_transport::ws::WsTransportClientBuilder,
+    core::{
+        client::{ClientT, ReceivedMessage, TransportReceiverT, TransportSenderT},
+        params::ArrayParams,
+        traits::ToRpcParams,
+    },
+    ws_client::{WsClient, WsClientBuilder},
+};
+use serde::de::DeserializeOwned;
+use tokio::sync::mpsc;
+use url::Url;
+
+use crate::{
+    core::{SubscriptionQuery, SubscriptionValue},
+    Error,
+};
+
+use super::{batch::send_batch, FromNotification, JsonRpcClient, Provider, PubsubClient, SubscriptionStream};
+
+/// The number of items a subscription buffers before it stops reading from its socket.
+const SUBSCRIPTION_BUFFER_SIZE: usize = 128;
+
+/// Websocket Provider
+///
+/// JSON-RPC requests are multiplexed over a single connection. Every subscription opens its own
+/// connection, so cancelling one doesn't affect the others.
+#[derive(Debug)]
+pub struct Ws {
+    client: WsClient,
+    url: Url,
+}
+
+impl Ws {
+    /// Connects to the given websocket endpoint.
+    pub async fn connect(url: impl Into<Url>) -> Result<Self, Error> {
+        let url = url.into();
+        Ok(Self {
+            client: WsClientBuilder::default().build(url.as_str()).await?,
+            url,
+        })
+    }
+}
+
+#[async_trait]
+impl JsonRpcClient for Ws {
+    async fn request<T: Send + Sync + ToRpcParams, R: DeserializeOwned>(&self, method: &str, params: T) -> Result<R, Error> {
+        self.client.request(method, params).await.map_err(Error::JsonRpcError)
+    }
+
+    async fn batch_request<R>(&self, requests: Vec<(String, ArrayParams)>) -> Result<Vec<Result<R, Error>>, Error>
+    where
+        R: DeserializeOwned + Debug + Send,
+    {
+        send_batch(&self.client, requests).await
+    }
+}
+
+#[async_trait]
+impl PubsubClient for Ws {
+    async fn subscribe<T: FromNotification>(&self, query: SubscriptionQuery) -> Result<SubscriptionStream<T>, Error> {
+        let (mut sender, mut receiver) = WsTransportClientBuilder::default().build(self.url.clone()).await?;
+        sender.send(serde_json::to_string(&query)?).await?;
+
+        let (tx, rx) = mpsc::channel(SUBSCRIPTION_BUFFER_SIZE);
+        tokio::spawn(async move {
+            loop {
+                let message = tokio::select! {
+                    _ = tx.closed() => break,
+                    message = receiver.receive() => message,
+                };
+
+                let items = match message {
+                    Ok(ReceivedMessage::Text(text)) => parse_notification(text.as_bytes(), query.topic()),
+                    Ok(ReceivedMessage::Bytes(bytes)) => parse_notification(&bytes, query.topic()),
+                    Ok(ReceivedMessage::Pong) => continue,
+                    Err(e) => {
+                        let _ = tx.send(Err(e.into())).await;
+                        return;
+                    }
+                };
+
+                for item in items {
+                    if tx.send(item).await.is_err() {
+                        break;
+                    }
+                }
+            }
+
+            let unsubscribe = SubscriptionQuery::Unsubscribe {
+                topic: query.topic().to_string(),
+            };
+            if let Ok(unsubscribe) = serde_json::to_string(&unsubscribe) {
+                let _ = sender.send(unsubscribe).await;
+            }
+            let _ = sender.close().await;
+        });
+
+        Ok(SubscriptionStream::new(rx))
+    }
+}
+
+/// Extracts the items of a notification that belong to the given topic. Anything other than a
+/// notification, e.g. the acknowledgement of a subscription, is ignored.
+fn parse_notification<T: FromNotification>(message: &[u8], topic: &str) -> Vec<Result<T, Error>> {
+    let message: serde_json::Value = match serde_json::from_slice(message) {
+        Ok(message) => message,
+        Err(e) => return vec![Err(e.into())],
+    };
+
+    if message.get("type").and_then(|t| t.as_str()) != Some("Notification") {
+        return vec![];
+    }
+
+    message
+        .get("values")
+        .and_then(|values| values.as_array())
+        .into_iter()
+        .flatten()
+        .filter(|value| value.get("query").and_then(|q| q.as_str()) == Some(topic))
+        .flat_map(|value| match serde_json::from_value::<SubscriptionValue>(value.clone()) {
+            Ok(value) => T::from_notification(value).into_iter().map(Ok).collect(),
+            Err(e) => vec![Err(e.into())],
+        })
+        .collect()
+}
+
+impl Provider<Ws> {
+    /// Creates a new Provider connected to the given websocket endpoint.
+    ///
+    /// # Example
+    /// ```no_run
+    /// use zilliqa_rs::providers::{Provider, Ws};
+    ///
+    /// #[tokio::main]
+    /// async fn main() -> anyhow::Result<()> {
+    ///     let provider = Provider::<Ws>::connect("wss://api-ws.zilliqa.com").await?.with_chain_id(1);
+    ///     Ok(())
+    /// }
+    /// ```
+    pub async fn connect(url: &str) -> Result<Self, Error> {
+        Ok(Provider::new(Ws::connect(Url::parse(url)?).await?, u16::default()))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::core::TxBlock;
+
+    use super::parse_notification;
+
+    #[test]
+    fn parse_notification_should_ignore_acknowledgements() {
+        let items = parse_notification::<TxBlock>(br#"{"query":"NewBlock"}"#, "NewBlock");
+        assert!(items.is_empty());
+    }
+
+    #[test]
+    fn parse_notification_should_ignore_other_topics() {
+        let items = parse_notification::<TxBlock>(
+            br#"{"type":"Notification","values":[{"query":"Unsubscribe","value":["EventLog"]},{"query":"Foo","value":1}]}"#,
+            "NewBlock",
+        );
+        assert!(items.is_empty());
+    }
+
+    #[test]
+    fn parse_notification_should_report_malformed_notifications() {
+        let items = parse_notification::<TxBlock>(
+            br#"{"type":"Notification","values":[{"query":"NewBlock","value":{}}]}"#,
+            "NewBlock",
+        );
+        assert_eq!(items.len(), 1);
+        assert!(items[0].is_err());
+    }
+}
@@ -1,15 +1,17 @@
 use std::{path::Path, str::FromStr};
 
+use async_trait::async_trait;
 use eth_keystore::{decrypt_key, encrypt_key};
 use k256::ecdsa::Signature;
 
 use crate::{
-    core::{PrivateKey, PublicKey, ZilAddress},
-    crypto::schnorr::sign,
+    core::{CreateTransactionRequest, PrivateKey, PublicKey, ZilAddress},
+    crypto::schnorr::{sign, sign_deterministic},
+    transaction::SignedTransaction,
     Error,
 };
 
-use super::Signer;
+use super::{MnemonicBuilder, Signer};
 
 /// Represents a local wallet, containing a private key, address, and public key.
 #[derive(Debug, Clone, PartialEq)]
@@ -20,6 +22,8 @@ pub struct LocalWallet {
     pub address: ZilAddress,
     /// Public key of the wallet.
     public_key: PublicKey,
+    /// Whether signatures use a nonce derived from the message instead of a random one.
+    deterministic: bool,
 }
 
 impl LocalWallet {
@@ -47,6 +51,32 @@ impl LocalWallet {
         PrivateKey::from_slice(&decrypt_key(path, password).unwrap())?.try_into()
     }
 
+    /// Derives a wallet from a BIP39 mnemonic phrase, at the given index of the Zilliqa derivation path
+    /// `m/44'/313'/0'/0/index`. Use [MnemonicBuilder](super::MnemonicBuilder) to set a passphrase too.
+    ///
+    /// # Example
+    /// ```
+    /// use zilliqa_rs::signers::LocalWallet;
+    /// let phrase = "abandon abandon abandon abandon abandon abandon abandon abandon abandon abandon abandon about";
+    /// let wallet = LocalWallet::from_mnemonic(phrase, 0).unwrap();
+    /// ```
+    pub fn from_mnemonic(phrase: &str, index: u32) -> Result<Self, Error> {
+        MnemonicBuilder::default().phrase(phrase).index(index).build()
+    }
+
+    /// Makes the wallet derive signing nonces from the private key and the message, as described in
+    /// RFC6979, so signing the same message twice results in the same signature.
+    ///
+    /// # Example
+    /// ```
+    /// use zilliqa_rs::signers::LocalWallet;
+    /// let wallet = LocalWallet::create_random().unwrap().with_deterministic_signing();
+    /// ```
+    pub fn with_deterministic_signing(mut self) -> Self {
+        self.deterministic = true;
+        self
+    }
+
     /// Encrypts the given wallet using the Scrypt password-based key derivation function, and stores it in the provided path. On success, it returns the id (Uuid) generated for this keystore.
     ///
     /// # Example
@@ -73,17 +103,88 @@ impl LocalWallet {
     }
 }
 
+impl LocalWallet {
+    /// Signs a transaction offline, without any network access.
+    ///
+    /// Since the network isn't queried, the version and the nonce of the transaction must be set. The
+    /// public key of the wallet is set as the sender of the transaction.
+    ///
+    /// # Example
+    /// ```
+    /// use zilliqa_rs::core::CreateTransactionRequest;
+    /// use zilliqa_rs::signers::LocalWallet;
+    /// use zilliqa_rs::transaction::Version;
+    ///
+    /// let wallet = LocalWallet::create_random().unwrap();
+    /// let tx = CreateTransactionRequest {
+    ///     version: Version::new(222),
+    ///     nonce: 1,
+    ///     to_addr: "0x381f4008505e940ad7681ec3468a719060caf796".parse().unwrap(),
+    ///     amount: 1_000_000_000_000,
+    ///     gas_price: 2_000_000_000,
+    ///     gas_limit: 50,
+    ///     ..Default::default()
+    /// };
+    /// let signed = wallet.sign_request(tx).unwrap();
+    /// assert!(signed.verify().is_ok());
+    /// ```
+    pub fn sign_request(&self, mut tx: CreateTransactionRequest) -> Result<SignedTransaction, Error> {
+        if !tx.version.is_valid() {
+            return Err(Error::InvalidVersionIsSetForTransaction(tx.version));
+        }
+
+        if tx.nonce == u64::default() {
+            return Err(Error::NonceIsNotSetForTransaction);
+        }
+
+        if let Some(from) = tx.from.take() {
+            self.check_address(&from)?;
+        }
+
+        tx.pub_key = Some(self.public_key.to_string());
+        let message = tx.proto_encode(self.public_key.to_sec1_bytes().into());
+        let signature = match self.deterministic {
+            true => sign_deterministic(&message, &self.private_key),
+            false => sign(&message, &self.private_key),
+        };
+        tx.signature = Some(hex::encode(signature.to_bytes()));
+
+        SignedTransaction::new(tx)
+    }
+
+    fn check_address(&self, address: &ZilAddress) -> Result<(), Error> {
+        if address != &self.address {
+            return Err(Error::AccountDoesNotExist(address.to_string()));
+        }
+        Ok(())
+    }
+}
+
+#[async_trait]
 impl Signer for LocalWallet {
-    fn sign(&self, message: &[u8]) -> Signature {
-        sign(message, &self.private_key)
+    async fn sign_for(&self, address: &ZilAddress, message: &[u8]) -> Result<Signature, Error> {
+        self.check_address(address)?;
+        self.sign(message).await
+    }
+
+    fn public_key_for(&self, address: &ZilAddress) -> Result<&PublicKey, Error> {
+        self.check_address(address)?;
+        Ok(&self.public_key)
+    }
+
+    fn address(&self) -> Result<&ZilAddress, Error> {
+        Ok(&self.address)
     }
 
-    fn address(&self) -> &ZilAddress {
-        &self.address
+    async fn sign(&self, message: &[u8]) -> Result<Signature, Error> {
+        if self.deterministic {
+            return Ok(sign_deterministic(message, &self.private_key));
+        }
+        Ok(sign(message, &self.private_key))
     }
 
-    fn public_key(&self) -> &PublicKey {
-        &self.public_key
+    fn public_key(&self) -> Result<&PublicKey, Error> {
+        Ok(&self.public_key)
     }
 }
 
@@ -116,6 +217,7 @@ impl TryFrom<PrivateKey> for LocalWallet {
             address,
             public_key: private_key.public_key(),
             private_key,
+            deterministic: false,
         })
     }
 }
@@ -136,7 +238,7 @@ mod tests {
             .parse()
             .unwrap();
         assert_eq!(
-            account.address(),
+            account.address().unwrap(),
             &"0x381f4008505e940AD7681EC3468a719060caF796".parse::<ZilAddress>().unwrap()
         );
     }
@@ -147,23 +249,23 @@ mod tests {
             .parse()
             .unwrap();
         assert_eq!(
-            account.address(),
+            account.address().unwrap(),
             &"0x381f4008505e940AD7681EC3468a719060caF796".parse::<ZilAddress>().unwrap()
         );
     }
 
-    #[test]
-    fn sign_should_return_signature() {
+    #[tokio::test]
+    async fn sign_should_return_signature() {
         let account: LocalWallet = "0xD96e9eb5b782a80ea153c937fa83e5948485fbfc8b7e7c069d7b914dbc350aba"
             .parse()
             .unwrap();
 
-        let signature = account.sign(&hex::decode("11223344aabb").unwrap());
+        let signature = account.sign(&hex::decode("11223344aabb").unwrap()).await.unwrap();
         println!("{} {}", signature.r().to_string(), signature.s().to_string());
 
         assert_some!(verify(
             &hex::decode("11223344aabb").unwrap(),
-            &account.public_key(),
+            account.public_key().unwrap(),
             &signature
         ));
     }
@@ -178,4 +280,21 @@ mod tests {
         let wallet2 = LocalWallet::load_keystore(&path, password).unwrap();
         assert_eq!(wallet.address, wallet2.address);
     }
+
+    #[tokio::test]
+    async fn deterministic_signing_should_return_the_same_signature() {
+        let account: LocalWallet = "0xD96e9eb5b782a80ea153c937fa83e5948485fbfc8b7e7c069d7b914dbc350aba"
+            .parse::<LocalWallet>()
+            .unwrap()
+            .with_deterministic_signing();
+
+        let message = hex::decode("11223344aabb").unwrap();
+        let signature = account.sign(&message).await.unwrap();
+
+        assert_eq!(signature, account.sign(&message).await.unwrap());
+        assert_eq!(
+            signature.r().to_string(),
+            "0B40E38F3355A703619984E2095A9579799092655E91DFB8D002260E96A28462"
+        );
+    }
 }
@@ -0,0 +1,196 @@
+//! Hierarchical deterministic wallets, derived from BIP39 mnemonic phrases.
+
+use coins_bip32::{ecdsa::SigningKey, xkeys::Parent};
+use coins_bip39::{English, Mnemonic};
+
+use crate::{core::PrivateKey, Error};
+
+use super::LocalWallet;
+
+/// BIP44 derivation path of Zilliqa accounts, without the account index. Zilliqa's coin type is 313.
+pub const ZILLIQA_DERIVATION_PATH: &str = "m/44'/313'/0'/0";
+
+/// Account indices are non-hardened BIP32 indices, so they must be below 2^31.
+const MAX_ACCOUNT_INDEX: u32 = 1 << 31;
+
+/// Builds [LocalWallet]s out of a BIP39 mnemonic phrase, deriving their keys with the BIP44 path
+/// `m/44'/313'/0'/0/index`, like Zilliqa wallets do.
+///
+/// # Example
+/// ```
+/// use zilliqa_rs::signers::MnemonicBuilder;
+///
+/// let phrase = MnemonicBuilder::generate_phrase(12).unwrap();
+/// let wallet = MnemonicBuilder::default().phrase(&phrase).index(1).build().unwrap();
+///
+/// let accounts = MnemonicBuilder::default().phrase(&phrase).build_accounts(3).unwrap();
+/// assert_eq!(accounts[1].address, wallet.address);
+/// ```
+#[derive(Default, Debug, Clone)]
+pub struct MnemonicBuilder {
+    phrase: Option<String>,
+    passphrase: Option<String>,
+    index: u32,
+}
+
+impl MnemonicBuilder {
+    /// Generates a random mnemonic phrase of English words. Word count must be 12, 15, 18, 21 or 24.
+    pub fn generate_phrase(word_count: usize) -> Result<String, Error> {
+        Ok(Mnemonic::<English>::new_with_count(&mut rand::thread_rng(), word_count)?.to_phrase())
+    }
+
+    /// Sets the mnemonic phrase to derive the wallets from.
+    pub fn phrase(mut self, phrase: &str) -> Self {
+        self.phrase = Some(phrase.to_string());
+        self
+    }
+
+    /// Sets the optional passphrase, known as the 25th word, which is mixed into the seed.
+    pub fn passphrase(mut self, passphrase: &str) -> Self {
+        self.passphrase = Some(passphrase.to_string());
+        self
+    }
+
+    /// Sets the index of the account to derive. It's 0 by default, and it must be below 2^31.
+    pub fn index(mut self, index: u32) -> Self {
+        self.index = index;
+        self
+    }
+
+    /// Derives the wallet at the index.
+    ///
+    /// It returns [Error::MnemonicIsNotSet] if no phrase is set, and an error if the phrase isn't a valid BIP39
+    /// English mnemonic.
+    pub fn build(&self) -> Result<LocalWallet, Error> {
+        Ok(self.build_accounts(1)?.remove(0))
+    }
+
+    /// Derives `count` consecutive wallets, starting from the index.
+    ///
+    /// It returns [Error::AccountIndexOutOfRange] if an index would reach 2^31, where hardened indices start.
+    pub fn build_accounts(&self, count: u32) -> Result<Vec<LocalWallet>, Error> {
+        let end = self
+            .index
+            .checked_add(count)
+            .filter(|end| *end <= MAX_ACCOUNT_INDEX)
+            .ok_or(Error::AccountIndexOutOfRange(self.index, count))?;
+        let phrase = self.phrase.as_deref().ok_or(Error::MnemonicIsNotSet)?;
+        let mnemonic = Mnemonic::<English>::new_from_phrase(phrase)?;
+        let parent = mnemonic.derive_key(ZILLIQA_DERIVATION_PATH, self.passphrase.as_deref())?;
+
+        (self.index..end)
+            .map(|index| {
+                let key = parent.derive_child(index)?;
+                let key: &SigningKey = key.as_ref();
+                PrivateKey::from_slice(&key.to_bytes())?.try_into()
+            })
+            .collect()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use claim::assert_matches;
+
+    use crate::{core::ZilAddress, Error};
+
+    use super::MnemonicBuilder;
+
+    const PHRASE: &str = "abandon abandon abandon abandon abandon abandon abandon abandon abandon abandon abandon about";
+
+    // Computed independently from the BIP39 and BIP32 specs, at m/44'/313'/0'/0/index.
+    // (phrase, passphrase, index, private key, address)
+    const VECTORS: [(&str, &str, u32, &str, &str); 4] = [
+        (
+            PHRASE,
+            "",
+            0,
+            "17f08231f4ae546f5d8d65d5dfa456fa999c8af629420d67c9af19161d78667f",
+            "0x21f0ca38bc8feb3155864763d3f39d4938f34e27",
+        ),
+        (
+            PHRASE,
+            "",
+            1,
+            "7e790298f776028caf50f1a3be83f176c8025038659f5932b679ea852521c2b2",
+            "0xa19889a09d44de7bc1c127448c272a80a27a4e22",
+        ),
+        (
+            PHRASE,
+            "TREZOR",
+            0,
+            "bd264936d39b0b371557bad3bcc3c70c458f9a9077fe16ee832bcb111f40be4c",
+            "0x922abb3fe09245765c2b002fdbf0ba2acd4fc54d",
+        ),
+        (
+            "legal winner thank year wave sausage worth useful legal winner thank yellow",
+            "",
+            0,
+            "9b6e11b4378a9e836be65f60faf42f25a1008b0e1f98781eccc487fed618276f",
+            "0xdda52b7725798135ae5c08395554dd5794764a93",
+        ),
+    ];
+
+    #[test]
+    fn wallets_should_be_derived_as_test_vectors() {
+        for (phrase, passphrase, index, private_key, address) in VECTORS {
+            let mut builder = MnemonicBuilder::default().phrase(phrase).index(index);
+            if !passphrase.is_empty() {
+                builder = builder.passphrase(passphrase);
+            }
+
+            let wallet = builder.build().unwrap();
+            assert_eq!(hex::encode(wallet.private_key.to_bytes()), private_key, "{phrase} {index}");
+            assert_eq!(wallet.address, address.parse::<ZilAddress>().unwrap(), "{phrase} {index}");
+        }
+    }
+
+    #[test]
+    fn build_accounts_should_derive_consecutive_indices() {
+        let accounts = MnemonicBuilder::default().phrase(PHRASE).build_accounts(2).unwrap();
+        assert_eq!(accounts[0].address, VECTORS[0].4.parse::<ZilAddress>().unwrap());
+        assert_eq!(accounts[1].address, VECTORS[1].4.parse::<ZilAddress>().unwrap());
+
+        let accounts = MnemonicBuilder::default().phrase(PHRASE).index(1).build_accounts(1).unwrap();
+        assert_eq!(accounts[0].address, VECTORS[1].4.parse::<ZilAddress>().unwrap());
+    }
+
+    #[test]
+    fn indices_should_stay_in_the_non_hardened_range() {
+        let builder = MnemonicBuilder::default().phrase(PHRASE);
+        assert!(builder.clone().index((1 << 31) - 1).build().is_ok());
+        assert_matches!(
+            builder.clone().index(1 << 31).build(),
+            Err(Error::AccountIndexOutOfRange(index, 1)) if index == 1 << 31
+        );
+        assert_matches!(
+            builder.clone().index((1 << 31) - 1).build_accounts(2),
+            Err(Error::AccountIndexOutOfRange(_, 2))
+        );
+        assert_matches!(
+            builder.index(u32::MAX).build_accounts(u32::MAX),
+            Err(Error::AccountIndexOutOfRange(u32::MAX, u32::MAX))
+        );
+    }
+
+    #[test]
+    fn generated_phrases_should_be_valid() {
+        for word_count in [12, 15, 18, 21, 24] {
+            let phrase = MnemonicBuilder::generate_phrase(word_count).unwrap();
+            assert_eq!(phrase.split_whitespace().count(), word_count);
+            assert!(MnemonicBuilder::default().phrase(&phrase).build().is_ok());
+        }
+        assert_matches!(MnemonicBuilder::generate_phrase(13), Err(Error::MnemonicError(_)));
+    }
+
+    #[test]
+    fn invalid_phrases_should_be_rejected() {
+        // The checksum of the last word doesn't match.
+        let phrase = "abandon abandon abandon abandon abandon abandon abandon abandon abandon abandon abandon abandon";
+        assert_matches!(
+            MnemonicBuilder::default().phrase(phrase).build(),
+            Err(Error::MnemonicError(_))
+        );
+        assert_matches!(MnemonicBuilder::default().build(), Err(Error::MnemonicIsNotSet));
+    }
+}
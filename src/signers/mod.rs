@@ -1,24 +1,56 @@
 //! A unified interface for locally signing zilliqa transactions.
 
 pub mod local_wallet;
+pub mod mnemonic;
 pub mod multi_account_wallet;
+pub mod remote;
 
 pub use local_wallet::LocalWallet;
+pub use mnemonic::MnemonicBuilder;
+pub use multi_account_wallet::MultiAccountWallet;
+pub use remote::RemoteSigner;
 
+use async_trait::async_trait;
 use k256::ecdsa::Signature;
 
 use crate::core::CreateTransactionRequest;
 use crate::core::{PublicKey, ZilAddress};
+use crate::Error;
 
 /// Trait for signing transactions and messages.
 ///
-/// Implement this trait to support different signing modes, e.g. Ledger, hosted etc.
-pub trait Signer {
-    fn sign(&self, message: &[u8]) -> Signature;
-    fn sign_transaction(&self, tx: &CreateTransactionRequest) -> Signature {
-        self.sign(&tx.proto_encode(self.public_key().to_sec1_bytes().into()))
+/// A signer may hold several accounts, one of which is the default account. Implement this trait to
+/// support different signing modes, e.g. Ledger, hosted etc. Signing is asynchronous and fallible, so
+/// the keys don't need to be available locally.
+#[async_trait]
+pub trait Signer: Send + Sync {
+    /// Signs the message with the account of the given address.
+    async fn sign_for(&self, address: &ZilAddress, message: &[u8]) -> Result<Signature, Error>;
+
+    /// Returns the public key of the account with the given address.
+    fn public_key_for(&self, address: &ZilAddress) -> Result<&PublicKey, Error>;
+
+    /// Returns the address of the default account.
+    fn address(&self) -> Result<&ZilAddress, Error>;
+
+    /// Signs the message with the default account.
+    async fn sign(&self, message: &[u8]) -> Result<Signature, Error> {
+        self.sign_for(self.address()?, message).await
     }
 
-    fn public_key(&self) -> &PublicKey;
-    fn address(&self) -> &ZilAddress;
+    /// Returns the public key of the default account.
+    fn public_key(&self) -> Result<&PublicKey, Error> {
+        self.public_key_for(self.address()?)
+    }
+
+    /// Signs the transaction with the account in its `from` field, or the default account if it's not set.
+    async fn sign_transaction(&self, tx: &CreateTransactionRequest) -> Result<Signature, Error> {
+        let address = match &tx.from {
+            Some(from) => from,
+            None => self.address()?,
+        };
+        let public_key = self.public_key_for(address)?;
+        self.sign_for(address, &tx.proto_encode(public_key.to_sec1_bytes().into()))
+            .await
+    }
 }
@@ -1,13 +1,23 @@
-use std::{collections::HashMap, rc::Rc};
+use std::{collections::HashMap, sync::Arc};
 
-use crate::{core::ZilAddress, Error};
+use async_trait::async_trait;
+use k256::ecdsa::Signature;
 
-use super::{LocalWallet, Signer};
+use crate::{
+    core::{PublicKey, ZilAddress},
+    Error,
+};
 
-#[derive(Default)]
+use super::{LocalWallet, MnemonicBuilder, Signer};
+
+/// A signer holding several accounts.
+///
+/// Transactions are signed by the account in their `from` field, or the default account if it's not set.
+/// The first added account becomes the default one.
+#[derive(Debug, Default)]
 pub struct MultiAccountWallet {
     default_account: Option<ZilAddress>,
-    accounts: HashMap<ZilAddress, Rc<LocalWallet>>,
+    accounts: HashMap<ZilAddress, Arc<LocalWallet>>,
 }
 
 impl MultiAccountWallet {
@@ -20,7 +30,7 @@ impl MultiAccountWallet {
 
         let accounts = accounts
             .into_iter()
-            .map(|account| (account.address.clone(), Rc::new(account)))
+            .map(|account| (account.address.clone(), Arc::new(account)))
             .collect::<HashMap<_, _>>();
 
         Self {
@@ -29,13 +39,33 @@ impl MultiAccountWallet {
         }
     }
 
-    pub fn create(&mut self) -> Result<Rc<LocalWallet>, Error> {
-        let wallet = Rc::new(LocalWallet::create_random()?);
+    /// Derives the first `count` accounts of a BIP39 mnemonic phrase. The first one becomes the default account.
+    ///
+    /// It returns [Error::NoAccountToDerive] if `count` is 0.
+    ///
+    /// # Example
+    /// ```
+    /// use zilliqa_rs::signers::MultiAccountWallet;
+    /// let phrase = "abandon abandon abandon abandon abandon abandon abandon abandon abandon abandon abandon about";
+    /// let wallet = MultiAccountWallet::from_mnemonic(phrase, 5).unwrap();
+    /// ```
+    pub fn from_mnemonic(phrase: &str, count: u32) -> Result<Self, Error> {
+        if count == 0 {
+            return Err(Error::NoAccountToDerive);
+        }
+
+        Ok(Self::new_with_accounts(
+            MnemonicBuilder::default().phrase(phrase).build_accounts(count)?,
+        ))
+    }
+
+    pub fn create(&mut self) -> Result<Arc<LocalWallet>, Error> {
+        let wallet = Arc::new(LocalWallet::create_random()?);
         self.add_local_wallet(wallet.clone());
         Ok(wallet.clone())
     }
 
-    pub fn add_local_wallet(&mut self, wallet: Rc<LocalWallet>) -> Rc<LocalWallet> {
+    pub fn add_local_wallet(&mut self, wallet: Arc<LocalWallet>) -> Arc<LocalWallet> {
         if self.default_account.is_none() {
             self.default_account = Some(wallet.address.clone())
         }
@@ -43,12 +73,12 @@ impl MultiAccountWallet {
         wallet
     }
 
-    pub fn add_by_private_key(&mut self, private_key: &str) -> Result<Rc<LocalWallet>, Error> {
+    pub fn add_by_private_key(&mut self, private_key: &str) -> Result<Arc<LocalWallet>, Error> {
         let wallet = private_key.parse::<LocalWallet>()?;
-        Ok(self.add_local_wallet(Rc::new(wallet)))
+        Ok(self.add_local_wallet(Arc::new(wallet)))
     }
 
-    pub fn remove(&mut self, address: &ZilAddress) -> Option<Rc<LocalWallet>> {
+    pub fn remove(&mut self, address: &ZilAddress) -> Option<Arc<LocalWallet>> {
         if let Some(account) = &self.default_account {
             if account == address {
                 self.default_account = None;
@@ -57,7 +87,7 @@ impl MultiAccountWallet {
         self.accounts.remove(address)
     }
 
-    pub fn set_default(&mut self, address: &ZilAddress) -> Result<Rc<LocalWallet>, Error> {
+    pub fn set_default(&mut self, address: &ZilAddress) -> Result<Arc<LocalWallet>, Error> {
         let account = self
             .accounts
             .get(address)
@@ -68,34 +98,47 @@ impl MultiAccountWallet {
         Ok(account.clone())
     }
 
-    pub fn default_account(&self) -> Option<Rc<LocalWallet>> {
+    pub fn default_account(&self) -> Option<Arc<LocalWallet>> {
         if let Some(address) = &self.default_account {
             self.accounts.get(address).cloned()
         } else {
             None
         }
     }
+
+    /// Returns the account with the given address.
+    pub fn account(&self, address: &ZilAddress) -> Result<&LocalWallet, Error> {
+        self.accounts
+            .get(address)
+            .map(|account| account.as_ref())
+            .ok_or(Error::AccountDoesNotExist(address.to_string()))
+    }
 }
 
+#[async_trait]
 impl Signer for MultiAccountWallet {
-    fn sign(&self, message: &[u8]) -> k256::ecdsa::Signature {
-        self.default_account().unwrap().sign(message)
+    async fn sign_for(&self, address: &ZilAddress, message: &[u8]) -> Result<Signature, Error> {
+        self.account(address)?.sign(message).await
     }
 
-    // FIXME:
-    fn public_key(&self) -> &crate::core::PublicKey {
-        todo!()
+    fn public_key_for(&self, address: &ZilAddress) -> Result<&PublicKey, Error> {
+        self.account(address)?.public_key()
     }
 
-    fn address(&self) -> &ZilAddress {
-        self.default_account.as_ref().unwrap()
+    fn address(&self) -> Result<&ZilAddress, Error> {
+        self.default_account.as_ref().ok_or(Error::DefaultAccountIsNotSet)
     }
 }
 
 #[cfg(test)]
 mod tests {
     use super::MultiAccountWallet;
-    use claim::assert_none;
+    use crate::{
+        crypto::schnorr::verify,
+        signers::{LocalWallet, Signer},
+        Error,
+    };
+    use claim::{assert_matches, assert_none, assert_some};
 
     #[test]
     fn wallet_create_function_should_create_a_new_account() {
@@ -154,4 +197,59 @@ mod tests {
         wallet.set_default(&local_wallet2.address).unwrap();
         assert_eq!(wallet.default_account().unwrap().address, local_wallet2.address);
     }
+
+    #[tokio::test]
+    async fn sign_for_should_sign_with_the_given_account() {
+        let mut wallet = MultiAccountWallet::default();
+        wallet.create().unwrap();
+        let local_wallet2 = wallet.create().unwrap();
+
+        let signature = wallet.sign_for(&local_wallet2.address, b"message").await.unwrap();
+        assert_some!(verify(b"message", local_wallet2.public_key().unwrap(), &signature));
+        assert_eq!(
+            wallet.public_key_for(&local_wallet2.address).unwrap(),
+            local_wallet2.public_key().unwrap()
+        );
+    }
+
+    #[tokio::test]
+    async fn signing_without_accounts_should_return_error() {
+        let wallet = MultiAccountWallet::default();
+        assert_matches!(wallet.sign(b"message").await, Err(Error::DefaultAccountIsNotSet));
+        assert_matches!(wallet.public_key(), Err(Error::DefaultAccountIsNotSet));
+        assert_matches!(
+            wallet
+                .sign_for(&"0x381f4008505e940AD7681EC3468a719060caF796".parse().unwrap(), b"message")
+                .await,
+            Err(Error::AccountDoesNotExist(_))
+        );
+    }
+
+    #[test]
+    fn from_mnemonic_should_derive_accounts_from_one_seed() {
+        let phrase = "abandon abandon abandon abandon abandon abandon abandon abandon abandon abandon abandon about";
+        let wallet = MultiAccountWallet::from_mnemonic(phrase, 3).unwrap();
+        assert_eq!(wallet.accounts.len(), 3);
+
+        for index in 0..3 {
+            let account = LocalWallet::from_mnemonic(phrase, index).unwrap();
+            assert_eq!(wallet.account(&account.address).unwrap(), &account);
+        }
+        assert_eq!(
+            wallet.default_account().unwrap().address,
+            LocalWallet::from_mnemonic(phrase, 0).unwrap().address
+        );
+    }
+
+    #[test]
+    fn from_mnemonic_should_reject_zero_accounts() {
+        let phrase = "abandon abandon abandon abandon abandon abandon abandon abandon abandon abandon abandon about";
+        assert_matches!(MultiAccountWallet::from_mnemonic(phrase, 0), Err(Error::NoAccountToDerive));
+    }
+
+    #[test]
+    fn wallet_should_be_send_and_sync() {
+        fn assert_send_sync<T: Send + Sync>() {}
+        assert_send_sync::<MultiAccountWallet>();
+    }
 }
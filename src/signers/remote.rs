@@ -0,0 +1,180 @@
+use async_trait::async_trait;
+use jsonrpsee::rpc_params;
+use k256::ecdsa::Signature;
+use url::Url;
+
+use crate::{
+    core::{PublicKey, ZilAddress},
+    crypto::schnorr::verify,
+    providers::{Http, JsonRpcClient},
+    Error,
+};
+
+use super::Signer;
+
+/// A signer which delegates signing to a signing service, e.g. a daemon in front of a KMS.
+///
+/// The service is called using JSON-RPC over HTTP and must support two methods:
+/// * `GetPublicKey`: Takes no parameters and returns the hex-encoded compressed public key of its account.
+/// * `Sign`: Takes the address of the account and the hex-encoded message, and returns the hex-encoded
+///   64-byte schnorr signature.
+///
+/// Signatures returned by the service are verified before being used.
+///
+/// # Example
+/// ```no_run
+/// use zilliqa_rs::providers::{Http, Provider};
+/// use zilliqa_rs::signers::RemoteSigner;
+/// use url::Url;
+///
+/// #[tokio::main]
+/// async fn main() -> anyhow::Result<()> {
+///     let signer = RemoteSigner::connect(Url::parse("http://127.0.0.1:7000")?).await?;
+///     let provider = Provider::<Http>::try_from("http://127.0.0.1:5555")?
+///         .with_chain_id(222)
+///         .with_signer(signer);
+///     Ok(())
+/// }
+/// ```
+#[derive(Debug)]
+pub struct RemoteSigner {
+    client: Http,
+    address: ZilAddress,
+    public_key: PublicKey,
+}
+
+impl RemoteSigner {
+    /// Connects to the signing service at the given URL and fetches the public key of its account.
+    pub async fn connect(url: impl Into<Url>) -> Result<Self, Error> {
+        let client = Http::new(url)?;
+        let public_key: PublicKey = client
+            .request::<_, String>("GetPublicKey", rpc_params![])
+            .await?
+            .trim_start_matches("0x")
+            .parse()?;
+
+        Ok(Self {
+            address: ZilAddress::try_from(&public_key)?,
+            public_key,
+            client,
+        })
+    }
+
+    fn check_address(&self, address: &ZilAddress) -> Result<(), Error> {
+        if address != &self.address {
+            return Err(Error::AccountDoesNotExist(address.to_string()));
+        }
+        Ok(())
+    }
+}
+
+#[async_trait]
+impl Signer for RemoteSigner {
+    async fn sign_for(&self, address: &ZilAddress, message: &[u8]) -> Result<Signature, Error> {
+        self.check_address(address)?;
+
+        let signature: String = self
+            .client
+            .request("Sign", rpc_params![address.to_string(), hex::encode(message)])
+            .await?;
+        let signature = Signature::from_slice(&hex::decode(signature.trim_start_matches("0x"))?)?;
+
+        verify(message, &self.public_key, &signature).ok_or(Error::InvalidSignature)?;
+        Ok(signature)
+    }
+
+    fn public_key_for(&self, address: &ZilAddress) -> Result<&PublicKey, Error> {
+        self.check_address(address)?;
+        Ok(&self.public_key)
+    }
+
+    fn address(&self) -> Result<&ZilAddress, Error> {
+        Ok(&self.address)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use claim::assert_matches;
+    use serde_json::{json, Value};
+    use url::Url;
+
+    use crate::{
+        core::CreateTransactionRequest,
+        crypto::schnorr::{sign, verify},
+        middlewares::{signer::SignerMiddleware, Middleware},
+        providers::{
+            mock::{serve_json_rpc, MockClient},
+            Provider,
+        },
+        signers::{LocalWallet, Signer},
+        Error,
+    };
+
+    use super::RemoteSigner;
+
+    const PRIVATE_KEY: &str = "d96e9eb5b782a80ea153c937fa83e5948485fbfc8b7e7c069d7b914dbc350aba";
+
+    /// A signing service holding the given wallet. Signatures are corrupted if `corrupt` is set.
+    async fn signing_service(corrupt: bool) -> String {
+        let wallet: LocalWallet = PRIVATE_KEY.parse().unwrap();
+        let (url, _) = serve_json_rpc(move |call| match call["method"].as_str() {
+            Some("GetPublicKey") => json!(wallet.public_key().unwrap().to_string()),
+            Some("Sign") => {
+                let message = hex::decode(call["params"][1].as_str().unwrap()).unwrap();
+                let message = if corrupt { b"another message".to_vec() } else { message };
+                let signature = sign(&message, &wallet.private_key);
+                json!(hex::encode(signature.to_bytes()))
+            }
+            _ => json!({"error": {"code": -32601, "message": "Method not found"}}),
+        })
+        .await;
+        url
+    }
+
+    #[tokio::test]
+    async fn remote_signer_should_sign_with_the_remote_account() {
+        let signer = RemoteSigner::connect(Url::parse(&signing_service(false).await).unwrap())
+            .await
+            .unwrap();
+        let wallet: LocalWallet = PRIVATE_KEY.parse().unwrap();
+        assert_eq!(signer.address().unwrap(), &wallet.address);
+
+        let signature = signer.sign(b"message").await.unwrap();
+        assert!(verify(b"message", wallet.public_key().unwrap(), &signature).is_some());
+    }
+
+    #[tokio::test]
+    async fn invalid_remote_signature_should_be_rejected() {
+        let signer = RemoteSigner::connect(Url::parse(&signing_service(true).await).unwrap())
+            .await
+            .unwrap();
+        assert_matches!(signer.sign(b"message").await, Err(Error::InvalidSignature));
+    }
+
+    #[tokio::test]
+    async fn signer_middleware_should_use_remote_signer() {
+        let signer = RemoteSigner::connect(Url::parse(&signing_service(false).await).unwrap())
+            .await
+            .unwrap();
+        let public_key = signer.public_key().unwrap().clone();
+
+        let mock = MockClient::default();
+        mock.push(json!({"TranID": "bdadfd994f452df803cc223d1f417b02830ac96dbe5edad1b9f8d58613f95206", "Info": ""}));
+        let provider = SignerMiddleware::new(Provider::new(mock.clone(), 222), signer);
+
+        let tx = CreateTransactionRequest {
+            nonce: 1,
+            to_addr: "0x381f4008505e940ad7681ec3468a719060caf796".parse().unwrap(),
+            amount: 1,
+            gas_price: 2000000000,
+            gas_limit: 50,
+            ..Default::default()
+        };
+        provider.send_transaction_without_confirm::<Value>(tx).await.unwrap();
+
+        let sent = &mock.requests()[0].1[0];
+        assert_eq!(sent["pubKey"], public_key.to_string());
+        assert!(sent["signature"].is_string());
+    }
+}
@@ -1,4 +1,3 @@
-use crate::core::parse_zil;
 use crate::core::{CreateTransactionRequest, ZilAddress};
 
 use super::Version;
@@ -15,6 +14,7 @@ pub struct TransactionParams {
     pub code: Option<String>,
     pub data: Option<String>,
     pub signature: Option<String>,
+    pub from: Option<ZilAddress>,
 }
 
 /// A builder to compose transaction.
@@ -90,6 +90,11 @@ pub struct TransactionBuilder {
 impl TransactionBuilder {
     /// Simplify payment transaction creation.
     ///
+    /// The gas price is left unset, so it's filled by a [GasOracleMiddleware], or the minimum gas price of the
+    /// network when the transaction is signed.
+    ///
+    /// [GasOracleMiddleware]: crate::middlewares::gas_oracle::GasOracleMiddleware
+    ///
     /// # Example
     /// ```
     /// use zilliqa_rs::transaction::TransactionBuilder;
@@ -104,7 +109,7 @@ impl TransactionBuilder {
     pub fn pay(mut self, amount: u128, to_addr: ZilAddress) -> Self {
         self.inner_transaction.amount = Some(amount);
         self.inner_transaction.to_addr = Some(to_addr);
-        self.gas_price_if_none(parse_zil("0.002").unwrap()).gas_limit_if_none(50u64)
+        self.gas_limit_if_none(50u64)
     }
 
     /// Sets the chain id of the final transaction request.
@@ -119,6 +124,12 @@ impl TransactionBuilder {
         self
     }
 
+    /// Sets the account which signs the final transaction request.
+    pub fn from_address(mut self, from: ZilAddress) -> Self {
+        self.inner_transaction.from = Some(from);
+        self
+    }
+
     /// Sets the destination of the final transaction request.
     pub fn to_address(mut self, to_addr: ZilAddress) -> Self {
         self.inner_transaction.to_addr = Some(to_addr);
@@ -225,6 +236,7 @@ impl TransactionBuilder {
             code: self.inner_transaction.code,
             data: self.inner_transaction.data,
             signature: self.inner_transaction.signature,
+            from: self.inner_transaction.from,
         }
     }
 }
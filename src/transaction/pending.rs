@@ -0,0 +1,404 @@
+use std::{
+    fmt,
+    future::Future,
+    pin::Pin,
+    sync::Mutex,
+    task::{Context, Poll},
+    time::Duration,
+};
+
+use futures::{future::BoxFuture, stream, FutureExt, Stream, StreamExt};
+use tokio::time::Instant;
+
+use crate::{
+    core::{GetTransactionResponse, TxHash, TxStatusCode},
+    middlewares::Middleware,
+    providers::{JsonRpcClient, Provider, RPCErrorCode},
+    Error,
+};
+
+/// Default time to wait between two status requests.
+pub const DEFAULT_POLLING_INTERVAL: Duration = Duration::from_secs(10);
+
+/// Default time to wait for a transaction to be confirmed.
+pub const DEFAULT_CONFIRMATION_TIMEOUT: Duration = Duration::from_secs(330);
+
+/// Status of a sent transaction, as reported by `GetTransactionStatus`.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum TxStatus {
+    /// The node doesn't know the transaction yet, or it's waiting to be dispatched.
+    Pending,
+    /// The transaction is dispatched to be processed.
+    Dispatched,
+    /// The transaction is processed, and waits for its tx block to be generated.
+    SoftConfirmed,
+    /// The transaction is included in a tx block. Its receipt tells if it's executed successfully.
+    Confirmed,
+    /// The transaction is rejected. The status code tells why.
+    Rejected(TxStatusCode),
+}
+
+impl From<TxStatusCode> for TxStatus {
+    fn from(code: TxStatusCode) -> Self {
+        match code {
+            TxStatusCode::Dispatched => Self::Dispatched,
+            TxStatusCode::SoftConfirmed => Self::SoftConfirmed,
+            TxStatusCode::Confirmed => Self::Confirmed,
+            code if code.is_rejected() => Self::Rejected(code),
+            _ => Self::Pending,
+        }
+    }
+}
+
+impl TxStatus {
+    /// Maps the `status` and `modificationState` codes returned by `GetTransactionStatus` to a status.
+    ///
+    /// The status code decides, see [TxStatusCode]. A status code which isn't final along with the final
+    /// modification state, i.e. 2, is a rejection with a code this crate doesn't know.
+    pub fn from_codes(status: u32, modification_state: u32) -> Self {
+        let code = TxStatusCode::from(status);
+        if modification_state == 2 && !code.is_final() {
+            return Self::Rejected(code);
+        }
+        code.into()
+    }
+
+    /// Checks if the status can't change anymore.
+    pub fn is_final(&self) -> bool {
+        matches!(self, Self::Confirmed | Self::Rejected(_))
+    }
+}
+
+/// A sent transaction, which is not confirmed yet.
+///
+/// Await it to wait for the transaction to be confirmed. Its status is polled with `GetTransactionStatus`
+/// every [PendingTransaction::with_interval], and once confirmed, the transaction and its receipt are fetched.
+/// It fails with [Error::TransactionRejected] if the transaction is rejected, and with
+/// [Error::TransactionTimedOut] if it's not confirmed within [PendingTransaction::with_timeout].
+///
+/// # Example
+/// ```no_run
+/// use std::time::Duration;
+/// use futures::StreamExt;
+/// use zilliqa_rs::core::TxHash;
+/// use zilliqa_rs::providers::{Http, Provider};
+/// use zilliqa_rs::transaction::PendingTransaction;
+///
+/// #[tokio::main]
+/// async fn main() -> anyhow::Result<()> {
+///     let provider = Provider::<Http>::try_from("http://127.0.0.1:5555")?.with_chain_id(222);
+///     let hash: TxHash = "d95f28e4585220fb2f368cfa4ddcc0890b7ac0a90a3e8735ab29aeaf554f9f66".parse()?;
+///     let tx = PendingTransaction::new(hash, &provider).with_interval(Duration::from_secs(2));
+///
+///     let mut statuses = Box::pin(tx.watch());
+///     while let Some(status) = statuses.next().await {
+///         println!("{:?}", status?);
+///     }
+///
+///     let tx = tx.await?;
+///     println!("{}", tx.receipt.success);
+///     Ok(())
+/// }
+/// ```
+pub struct PendingTransaction<'a, T: JsonRpcClient> {
+    /// Hash of the transaction.
+    pub id: TxHash,
+    /// Client to confirm transaction.
+    client: &'a Provider<T>,
+    interval: Duration,
+    timeout: Duration,
+    /// Confirmation of the transaction, started when it's first polled. It's only accessed through
+    /// [Mutex::get_mut], the mutex just keeps the pending transaction `Sync`.
+    #[allow(clippy::type_complexity)]
+    confirmation: Mutex<Option<BoxFuture<'a, Result<GetTransactionResponse, Error>>>>,
+}
+
+impl<'a, T: JsonRpcClient> PendingTransaction<'a, T> {
+    /// Creates a new pending transaction.
+    ///
+    /// To create a new pending transaction you need to pass a hash, which identifies a transaction uniquely, and a
+    /// provider, which is used to confirm the transaction.
+    pub fn new(id: TxHash, client: &'a Provider<T>) -> Self {
+        Self {
+            id,
+            client,
+            interval: DEFAULT_POLLING_INTERVAL,
+            timeout: DEFAULT_CONFIRMATION_TIMEOUT,
+            confirmation: Mutex::new(None),
+        }
+    }
+
+    /// Sets the time to wait between two status requests.
+    pub fn with_interval(mut self, interval: Duration) -> Self {
+        self.interval = interval;
+        self
+    }
+
+    /// Sets the time to wait for the transaction to be confirmed, from when it's first awaited or watched.
+    pub fn with_timeout(mut self, timeout: Duration) -> Self {
+        self.timeout = timeout;
+        self
+    }
+
+    /// Returns the current status of the transaction.
+    pub async fn status(&self) -> Result<TxStatus, Error> {
+        fetch_status(self.client, &self.id).await
+    }
+
+    /// Streams the status of the transaction whenever it changes, starting with the current one.
+    ///
+    /// The stream ends after a final status, i.e. [TxStatus::Confirmed] or [TxStatus::Rejected], or an error.
+    /// If the status isn't final within the timeout, [Error::TransactionTimedOut] is the last item.
+    pub fn watch(&self) -> impl Stream<Item = Result<TxStatus, Error>> + Send + 'a {
+        watch_status(self.client, self.id.clone(), self.interval, Instant::now() + self.timeout)
+    }
+}
+
+impl<T: JsonRpcClient> fmt::Debug for PendingTransaction<'_, T> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("PendingTransaction")
+            .field("id", &self.id)
+            .field("client", &self.client)
+            .field("interval", &self.interval)
+            .field("timeout", &self.timeout)
+            .finish_non_exhaustive()
+    }
+}
+
+impl<'a, T: JsonRpcClient> Future for PendingTransaction<'a, T> {
+    type Output = Result<GetTransactionResponse, Error>;
+
+    fn poll(self: Pin<&mut Self>, cx: &mut Context<'_>) -> Poll<Self::Output> {
+        let this = self.get_mut();
+        let confirmation = this.confirmation.get_mut().unwrap_or_else(|e| e.into_inner());
+        confirmation
+            .get_or_insert_with(|| confirm(this.client, this.id.clone(), this.interval, Instant::now() + this.timeout).boxed())
+            .poll_unpin(cx)
+    }
+}
+
+/// Checks if the node failed the request because it doesn't know the transaction.
+fn is_not_found(error: &Error) -> bool {
+    RPCErrorCode::from_error(error) == Some(RPCErrorCode::RpcDatabaseError)
+}
+
+async fn fetch_status<T: JsonRpcClient>(client: &Provider<T>, id: &TxHash) -> Result<TxStatus, Error> {
+    match client.get_transaction_status(id).await {
+        Ok(status) => Ok(TxStatus::from_codes(status.status, status.modification_state)),
+        Err(e) if is_not_found(&e) => Ok(TxStatus::Pending),
+        Err(e) => Err(e),
+    }
+}
+
+fn watch_status<T: JsonRpcClient>(
+    client: &Provider<T>,
+    id: TxHash,
+    interval: Duration,
+    deadline: Instant,
+) -> impl Stream<Item = Result<TxStatus, Error>> + Send + '_ {
+    stream::unfold(Some(None), move |last: Option<Option<TxStatus>>| {
+        let id = id.clone();
+        async move {
+            // `None` means the stream is over. Otherwise it holds the last yielded status.
+            let last = last?;
+            loop {
+                if last.is_some() {
+                    if Instant::now() + interval > deadline {
+                        return Some((Err(Error::TransactionTimedOut(id)), None));
+                    }
+                    tokio::time::sleep(interval).await;
+                }
+
+                match fetch_status(client, &id).await {
+                    Ok(status) if status.is_final() => return Some((Ok(status), None)),
+                    Ok(status) if Some(status) != last => return Some((Ok(status), Some(Some(status)))),
+                    Ok(_) => continue,
+                    Err(e) => return Some((Err(e), None)),
+                }
+            }
+        }
+    })
+}
+
+async fn confirm<T: JsonRpcClient>(
+    client: &Provider<T>,
+    id: TxHash,
+    interval: Duration,
+    deadline: Instant,
+) -> Result<GetTransactionResponse, Error> {
+    let mut statuses = Box::pin(watch_status(client, id.clone(), interval, deadline));
+    while let Some(status) = statuses.next().await {
+        if let TxStatus::Rejected(status) = status? {
+            return Err(Error::TransactionRejected(id, status));
+        }
+    }
+
+    // The transaction may be confirmed slightly before its body is queryable.
+    loop {
+        match client.get_transaction(&id).await {
+            Err(e) if is_not_found(&e) => {
+                if Instant::now() + interval > deadline {
+                    return Err(Error::TransactionTimedOut(id));
+                }
+                tokio::time::sleep(interval).await;
+            }
+            result => return result,
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::time::Duration;
+
+    use claim::assert_matches;
+    use futures::TryStreamExt;
+    use jsonrpsee::{core::ClientError, types::ErrorObject};
+    use serde_json::{json, Value};
+
+    use crate::{
+        core::{TxHash, TxStatusCode},
+        providers::{
+            mock::{fixture, MockClient},
+            Provider,
+        },
+        Error,
+    };
+
+    use super::{PendingTransaction, TxStatus};
+
+    const HASH: &str = "d95f28e4585220fb2f368cfa4ddcc0890b7ac0a90a3e8735ab29aeaf554f9f66";
+
+    fn status(status: u32, modification_state: u32) -> Value {
+        fixture(
+            "get_transaction_status",
+            &[("/status", json!(status)), ("/modificationState", json!(modification_state))],
+        )
+    }
+
+    fn not_found() -> ClientError {
+        ClientError::Call(ErrorObject::owned(-20, "Txn Hash not Present", None::<()>))
+    }
+
+    fn pending_transaction(provider: &Provider<MockClient>) -> PendingTransaction<'_, MockClient> {
+        PendingTransaction::new(HASH.parse::<TxHash>().unwrap(), provider).with_interval(Duration::from_millis(1))
+    }
+
+    #[test]
+    fn status_codes_should_be_mapped_to_statuses() {
+        assert_eq!(TxStatus::from_codes(0, 0), TxStatus::Pending);
+        assert_eq!(TxStatus::from_codes(1, 1), TxStatus::Dispatched);
+        assert_eq!(TxStatus::from_codes(2, 1), TxStatus::SoftConfirmed);
+        assert_eq!(TxStatus::from_codes(4, 0), TxStatus::Pending);
+        assert_eq!(TxStatus::from_codes(3, 2), TxStatus::Confirmed);
+        assert_eq!(
+            TxStatus::from_codes(21, 2),
+            TxStatus::Rejected(TxStatusCode::InsufficientBalance)
+        );
+        assert_eq!(TxStatus::from_codes(99, 2), TxStatus::Rejected(TxStatusCode::Unknown(99)));
+    }
+
+    #[test]
+    fn statuses_should_agree_with_status_codes() {
+        for code in (0..=30).chain([255]).map(TxStatusCode::from) {
+            let status = TxStatus::from(code);
+            assert_eq!(status.is_final(), code.is_final(), "{code}");
+            assert_eq!(matches!(status, TxStatus::Rejected(_)), code.is_rejected(), "{code}");
+        }
+    }
+
+    #[tokio::test]
+    async fn watch_should_yield_status_changes_until_final() {
+        let mock = MockClient::default();
+        mock.push_error(not_found());
+        mock.push(status(1, 1));
+        mock.push(status(1, 1));
+        mock.push(status(2, 1));
+        mock.push(status(3, 2));
+
+        let provider = Provider::new(mock.clone(), 222);
+        let statuses: Vec<_> = pending_transaction(&provider).watch().try_collect().await.unwrap();
+
+        assert_eq!(
+            statuses,
+            vec![
+                TxStatus::Pending,
+                TxStatus::Dispatched,
+                TxStatus::SoftConfirmed,
+                TxStatus::Confirmed
+            ]
+        );
+        assert_eq!(mock.requests().len(), 5);
+    }
+
+    #[tokio::test]
+    async fn awaiting_should_return_the_confirmed_transaction() {
+        let mock = MockClient::default();
+        mock.push(status(2, 1));
+        mock.push(status(3, 2));
+        mock.push_error(not_found());
+        mock.push(fixture("get_transaction", &[]));
+
+        let provider = Provider::new(mock.clone(), 222);
+        let tx = pending_transaction(&provider).await.unwrap();
+
+        assert!(tx.receipt.success);
+        let methods: Vec<_> = mock.requests().into_iter().map(|(method, _)| method).collect();
+        assert_eq!(
+            methods,
+            vec![
+                "GetTransactionStatus",
+                "GetTransactionStatus",
+                "GetTransaction",
+                "GetTransaction"
+            ]
+        );
+    }
+
+    #[tokio::test]
+    async fn rejected_transaction_should_fail_with_its_status_code() {
+        let mock = MockClient::default();
+        mock.push(status(1, 1));
+        mock.push(status(21, 2));
+
+        let provider = Provider::new(mock.clone(), 222);
+        assert_matches!(
+            pending_transaction(&provider).await,
+            Err(Error::TransactionRejected(_, TxStatusCode::InsufficientBalance))
+        );
+    }
+
+    #[tokio::test]
+    async fn rpc_errors_should_not_be_taken_as_not_found() {
+        let mock = MockClient::default();
+        mock.push_error(ClientError::Call(ErrorObject::owned(-32602, "Invalid params", None::<()>)));
+
+        let provider = Provider::new(mock.clone(), 222);
+        assert_matches!(
+            pending_transaction(&provider).await,
+            Err(Error::JsonRpcError(ClientError::Call(_)))
+        );
+    }
+
+    #[tokio::test]
+    async fn unconfirmed_transaction_should_time_out() {
+        let mock = MockClient::default();
+        for _ in 0..10 {
+            mock.push(status(1, 1));
+        }
+
+        let provider = Provider::new(mock.clone(), 222);
+        let tx = PendingTransaction::new(HASH.parse().unwrap(), &provider)
+            .with_interval(Duration::from_millis(20))
+            .with_timeout(Duration::from_millis(50));
+
+        assert_matches!(tx.await, Err(Error::TransactionTimedOut(_)));
+        assert!(mock.requests().len() < 10);
+    }
+
+    #[test]
+    fn pending_transaction_should_be_send_and_sync() {
+        fn assert_send_sync<T: Send + Sync>() {}
+        assert_send_sync::<PendingTransaction<'static, MockClient>>();
+    }
+}
@@ -0,0 +1,249 @@
+use prost::Message;
+use serde::{Deserialize, Serialize};
+use sha2::{Digest, Sha256};
+
+use crate::{
+    core::{proto, CreateTransactionRequest, PublicKey, TxHash},
+    crypto::{schnorr::verify, Signature},
+    Error,
+};
+
+/// A transaction signed offline, ready to be broadcast.
+///
+/// A signed transaction can be exported to JSON or protobuf, e.g. to be moved from an air-gapped machine, and
+/// imported back elsewhere. The signature is verified whenever a signed transaction is created or imported, so
+/// a [SignedTransaction] always carries a valid signature.
+///
+/// # Example
+/// ```
+/// use zilliqa_rs::core::CreateTransactionRequest;
+/// use zilliqa_rs::signers::LocalWallet;
+/// use zilliqa_rs::transaction::{SignedTransaction, Version};
+///
+/// let wallet = LocalWallet::create_random().unwrap();
+/// let tx = CreateTransactionRequest {
+///     version: Version::new(222),
+///     nonce: 1,
+///     to_addr: "0x381f4008505e940ad7681ec3468a719060caf796".parse().unwrap(),
+///     amount: 1_000_000_000_000,
+///     gas_price: 2_000_000_000,
+///     gas_limit: 50,
+///     ..Default::default()
+/// };
+///
+/// let signed = wallet.sign_request(tx).unwrap();
+/// let imported = SignedTransaction::from_json(&signed.to_json().unwrap()).unwrap();
+/// assert_eq!(imported.tx_hash().to_string(), signed.tx_hash().to_string());
+/// ```
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(into = "CreateTransactionRequest", try_from = "CreateTransactionRequest")]
+pub struct SignedTransaction {
+    request: CreateTransactionRequest,
+    public_key: PublicKey,
+    signature: Signature,
+}
+
+impl SignedTransaction {
+    /// Creates a signed transaction out of a request which has its public key and signature set.
+    ///
+    /// Returns an error if the signature doesn't match the transaction.
+    pub fn new(request: CreateTransactionRequest) -> Result<Self, Error> {
+        let (public_key, signature) = match (&request.pub_key, &request.signature) {
+            (Some(public_key), Some(signature)) => (public_key.parse::<PublicKey>()?, signature),
+            _ => return Err(Error::TransactionIsNotSigned),
+        };
+        let signature = Signature::from_slice(&hex::decode(signature.trim_start_matches("0x"))?)?;
+
+        let signed = Self {
+            request,
+            public_key,
+            signature,
+        };
+        signed.verify()?;
+        Ok(signed)
+    }
+
+    /// The underlying request, as it's sent to the network.
+    pub fn request(&self) -> &CreateTransactionRequest {
+        &self.request
+    }
+
+    /// Consumes the signed transaction and returns the underlying request.
+    pub fn into_request(self) -> CreateTransactionRequest {
+        self.request
+    }
+
+    /// Public key of the signer.
+    pub fn public_key(&self) -> &PublicKey {
+        &self.public_key
+    }
+
+    /// Schnorr signature of the transaction.
+    pub fn signature(&self) -> &Signature {
+        &self.signature
+    }
+
+    /// Verifies the signature against the transaction and the public key of the signer.
+    pub fn verify(&self) -> Result<(), Error> {
+        verify(&self.core_info_bytes(), &self.public_key, &self.signature).ok_or(Error::InvalidSignature)
+    }
+
+    /// Hash of the transaction, which is the ID the network assigns to it.
+    pub fn tx_hash(&self) -> TxHash {
+        TxHash::from_core_info(&self.core_info_bytes())
+    }
+
+    /// Serializes the transaction to the JSON accepted by `CreateTransaction`.
+    pub fn to_json(&self) -> Result<String, Error> {
+        Ok(serde_json::to_string(&self.request)?)
+    }
+
+    /// Parses a transaction serialized by [SignedTransaction::to_json] and verifies its signature.
+    pub fn from_json(json: &str) -> Result<Self, Error> {
+        Ok(serde_json::from_str(json)?)
+    }
+
+    /// Serializes the transaction to protobuf, including its hash and signature.
+    pub fn to_proto(&self) -> Vec<u8> {
+        let core_info = self.core_info_bytes();
+        proto::ProtoTransaction {
+            tranid: Sha256::digest(&core_info).to_vec(),
+            info: Some(proto::ProtoTransactionCoreInfo::decode(core_info.as_slice()).expect("encoded by ourselves")),
+            signature: Some(self.signature.to_bytes().to_vec().into()),
+        }
+        .encode_to_vec()
+    }
+
+    /// Parses a transaction serialized by [SignedTransaction::to_proto] and verifies its signature.
+    pub fn from_proto(bytes: &[u8]) -> Result<Self, Error> {
+        let proto = proto::ProtoTransaction::decode(bytes)?;
+        let mut request = CreateTransactionRequest::proto_decode(proto.info.ok_or(Error::TransactionIsNotSigned)?)?;
+        request.signature = proto.signature.map(|signature| hex::encode(signature.data));
+        Self::new(request)
+    }
+
+    fn core_info_bytes(&self) -> Vec<u8> {
+        self.request.proto_encode(self.public_key.to_sec1_bytes().into())
+    }
+}
+
+impl TryFrom<CreateTransactionRequest> for SignedTransaction {
+    type Error = Error;
+
+    fn try_from(request: CreateTransactionRequest) -> Result<Self, Self::Error> {
+        Self::new(request)
+    }
+}
+
+impl From<SignedTransaction> for CreateTransactionRequest {
+    fn from(signed: SignedTransaction) -> Self {
+        signed.request
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use claim::assert_matches;
+    use serde_json::json;
+
+    use crate::{
+        core::CreateTransactionRequest,
+        providers::{mock::MockClient, Provider},
+        signers::LocalWallet,
+        transaction::Version,
+        Error,
+    };
+
+    use super::SignedTransaction;
+
+    fn wallet() -> LocalWallet {
+        "d96e9eb5b782a80ea153c937fa83e5948485fbfc8b7e7c069d7b914dbc350aba"
+            .parse::<LocalWallet>()
+            .unwrap()
+            .with_deterministic_signing()
+    }
+
+    fn transfer() -> CreateTransactionRequest {
+        CreateTransactionRequest {
+            version: Version::new(222),
+            nonce: 3,
+            to_addr: "0x381f4008505e940ad7681ec3468a719060caf796".parse().unwrap(),
+            amount: 1_000_000_000_000,
+            gas_price: 2_000_000_000,
+            gas_limit: 50,
+            data: Some(r#"{"_tag":"Transfer","params":[]}"#.to_string()),
+            ..Default::default()
+        }
+    }
+
+    #[test]
+    fn json_export_should_round_trip() {
+        let signed = wallet().sign_request(transfer()).unwrap();
+        let imported = SignedTransaction::from_json(&signed.to_json().unwrap()).unwrap();
+
+        assert_eq!(imported.tx_hash().to_string(), signed.tx_hash().to_string());
+        assert_eq!(imported.signature(), signed.signature());
+        assert_eq!(imported.request().amount, 1_000_000_000_000);
+    }
+
+    #[test]
+    fn proto_export_should_round_trip() {
+        let signed = wallet().sign_request(transfer()).unwrap();
+        let imported = SignedTransaction::from_proto(&signed.to_proto()).unwrap();
+
+        assert_eq!(imported.tx_hash().to_string(), signed.tx_hash().to_string());
+        assert_eq!(imported.public_key(), signed.public_key());
+        assert_eq!(imported.request().data, signed.request().data);
+        assert_eq!(imported.to_json().unwrap(), signed.to_json().unwrap());
+    }
+
+    #[test]
+    fn tampered_transaction_should_be_rejected() {
+        let signed = wallet().sign_request(transfer()).unwrap();
+        let mut json: serde_json::Value = serde_json::from_str(&signed.to_json().unwrap()).unwrap();
+        json["amount"] = json!("2000000000000");
+
+        assert_matches!(SignedTransaction::from_json(&json.to_string()), Err(Error::JsonError(_)));
+        let mut request = signed.into_request();
+        request.amount = 2_000_000_000_000;
+        assert_matches!(SignedTransaction::new(request), Err(Error::InvalidSignature));
+    }
+
+    #[test]
+    fn unsigned_transaction_should_be_rejected() {
+        assert_matches!(SignedTransaction::new(transfer()), Err(Error::TransactionIsNotSigned));
+    }
+
+    #[test]
+    fn sign_request_should_require_nonce_and_version() {
+        let tx = CreateTransactionRequest { nonce: 0, ..transfer() };
+        assert_matches!(wallet().sign_request(tx), Err(Error::NonceIsNotSetForTransaction));
+
+        let tx = CreateTransactionRequest {
+            version: Version::default(),
+            ..transfer()
+        };
+        assert_matches!(wallet().sign_request(tx), Err(Error::InvalidVersionIsSetForTransaction(_)));
+    }
+
+    #[tokio::test]
+    async fn send_raw_should_broadcast_the_signed_transaction() {
+        let mock = MockClient::default();
+        mock.push(json!({"TranID": "bdadfd994f452df803cc223d1f417b02830ac96dbe5edad1b9f8d58613f95206", "Info": ""}));
+
+        let signed = wallet().sign_request(transfer()).unwrap();
+        let provider = Provider::new(mock.clone(), 222);
+        let tx = provider.send_raw(&signed).await.unwrap();
+
+        assert_eq!(
+            tx.id.to_string(),
+            "bdadfd994f452df803cc223d1f417b02830ac96dbe5edad1b9f8d58613f95206"
+        );
+        let (method, params) = &mock.requests()[0];
+        assert_eq!(method, "CreateTransaction");
+        assert_eq!(
+            params[0],
+            serde_json::from_str::<serde_json::Value>(&signed.to_json().unwrap()).unwrap()
+        );
+    }
+}
@@ -2,6 +2,7 @@ use std::str::FromStr;
 
 use claim::assert_ok;
 use paste::paste;
+use zilliqa_rs::core::BlockId;
 use zilliqa_rs::core::TxHash;
 use zilliqa_rs::core::ZilAddress;
 use zilliqa_rs::middlewares::Middleware;
@@ -62,15 +63,15 @@ macro_rules! rpc_method_test_ignored {
 rpc_method_test!(GetBalance, "zil12mawdph2r00wys4q68jfsay2jy374lv6c3j5ek");
 rpc_method_test!(GetBlockchainInfo);
 rpc_method_test!(GetShardingStructure);
-rpc_method_test!(GetDsBlock, "1");
-rpc_method_test!(GetDsBlockVerbose, "9000");
+rpc_method_test!(GetDsBlock, BlockId::Number(1));
+rpc_method_test!(GetDsBlockVerbose, BlockId::Number(9000));
 rpc_method_test!(GetLatestDsBlock);
 rpc_method_test!(GetNodeType);
 rpc_method_test!(GetNumDsBlocks);
 rpc_method_test!(GetDsBlockRate);
 rpc_method_test!(DsBlockListing, 1);
-rpc_method_test!(GetTxBlock, "1");
-rpc_method_test!(GetTxBlockVerbose, "1");
+rpc_method_test!(GetTxBlock, BlockId::Number(1));
+rpc_method_test!(GetTxBlockVerbose, BlockId::Number(1));
 rpc_method_test!(GetLatestTxBlock);
 rpc_method_test!(GetNumTxBlocks);
 rpc_method_test!(GetTxBlockRate);
@@ -85,14 +86,14 @@ rpc_method_test!(GetPrevDsDifficulty);
 rpc_method_test!(GetTotalCoinSupply);
 rpc_method_test!(GetTotalCoinSupplyAsInt);
 rpc_method_test!(GetCurrentDsComm);
-rpc_method_test!(GetMinerInfo, "5500");
+rpc_method_test!(GetMinerInfo, BlockId::Number(5500));
 rpc_method_test!(GetRecentTransactions);
-rpc_method_test!(GetTransactionsForTxBlock, "3357911");
-rpc_method_test!(GetTransactionsForTxBlockEx, "3357911", "0");
-rpc_method_test!(GetTxnBodiesForTxBlock, "3357911");
-rpc_method_test!(GetTxnBodiesForTxBlockEx, "3357911", "0");
-rpc_method_test!(GetNumTxnsTxEpoch, "1");
-rpc_method_test!(GetNumTxnsDsEpoch, "1");
+rpc_method_test!(GetTransactionsForTxBlock, BlockId::Number(3357911));
+rpc_method_test!(GetTransactionsForTxBlockEx, BlockId::Number(3357911), 0);
+rpc_method_test!(GetTxnBodiesForTxBlock, BlockId::Number(3357911));
+rpc_method_test!(GetTxnBodiesForTxBlockEx, BlockId::Number(3357911), 0);
+rpc_method_test!(GetNumTxnsTxEpoch, BlockId::Number(1));
+rpc_method_test!(GetNumTxnsDsEpoch, BlockId::Number(1));
 rpc_method_test!(GetMinimumGasPrice);
 rpc_method_test!(
     GetTransactionStatus,
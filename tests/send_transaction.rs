@@ -31,8 +31,7 @@ async fn send_transaction(ctx: &TestContext) -> Result<()> {
         .gas_limit(50u64)
         .build();
 
-    let tx = provider.send_transaction(tx).await?;
-    tx.confirm().await?;
+    provider.send_transaction(tx).await?.await?;
 
     let res = provider.get_balance(&receiver.address).await?;
 
@@ -112,7 +111,7 @@ async fn get_transaction_receipt() -> Result<()> {
     let tx = TransactionBuilder::default().pay(amount, receiver.address.clone()).build();
     let tx = provider.send_transaction(tx).await?;
 
-    let res = tx.confirm().await?;
+    let res = tx.await?;
     println!("{:?}", res.receipt);
 
     let sender_balance = provider.get_balance(&receiver.address).await?;